@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use axum::{routing::get_service, Router};
+use axum::Router;
 use clap::{Parser, Subcommand};
 use std::fs;
 use std::net::SocketAddr;
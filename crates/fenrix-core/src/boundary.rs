@@ -0,0 +1,150 @@
+//! The reactive side of suspense and error boundaries.
+//!
+//! A boundary is attached to a scope and covers everything created in it.
+//! Resources read inside a [`SuspenseContext`] are tracked by it, and errors
+//! passed to [`throw_error`] are caught by the nearest [`ErrorBoundaryContext`].
+//! The `<Suspense>` and `<ErrorBoundary>` components in `fenrix_dom` use these
+//! to decide what to render.
+
+use crate::owner::current_owner;
+use crate::signal::{ReadSignal, RwSignal};
+use crate::{create_rw_signal, create_scope, untrack};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+/// An error caught by an error boundary.
+///
+/// It is cheap to clone; all clones refer to the same error.
+#[derive(Clone)]
+pub struct CapturedError(Rc<dyn Error>);
+
+impl CapturedError {
+    /// Wraps any error, or a plain message such as a `String`.
+    pub fn new(error: impl Into<Box<dyn Error>>) -> Self {
+        CapturedError(Rc::from(error.into()))
+    }
+
+    /// Returns the underlying error.
+    pub fn error(&self) -> &(dyn Error + 'static) {
+        &*self.0
+    }
+}
+
+impl fmt::Display for CapturedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for CapturedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// Tracks the resources read inside a suspense boundary.
+#[derive(Clone, Copy, Debug)]
+pub struct SuspenseContext {
+    // The `loading` signal of every resource read inside the boundary.
+    resources: RwSignal<Vec<ReadSignal<bool>>>,
+}
+
+impl SuspenseContext {
+    /// Creates a suspense context owned by the current scope.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        SuspenseContext {
+            resources: create_rw_signal(Vec::new()),
+        }
+    }
+
+    /// Runs `f` in a new scope covered by this context, so that resources
+    /// read by anything it creates are tracked here.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        let (result, _) = create_scope(|| {
+            current_owner().provide_context(*self);
+            f()
+        });
+        result
+    }
+
+    /// Returns `true` while any resource read inside the boundary is loading.
+    pub fn is_pending(&self) -> bool {
+        self.resources.with(|resources| {
+            resources
+                .iter()
+                .any(|loading| loading.try_with(|loading| *loading).unwrap_or(false))
+        })
+    }
+
+    // Starts tracking a resource, identified by its `loading` signal.
+    fn register(&self, loading: ReadSignal<bool>) {
+        let is_new = untrack(|| self.resources.with(|r| !r.contains(&loading)));
+        if is_new {
+            self.resources.update(|r| r.push(loading));
+        }
+    }
+}
+
+// Called whenever a resource is read, so the nearest suspense boundary waits
+// for it.
+pub(crate) fn register_resource(loading: ReadSignal<bool>) {
+    if let Some(suspense) = current_owner().use_context::<SuspenseContext>() {
+        suspense.register(loading);
+    }
+}
+
+/// Holds the error caught by an error boundary.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorBoundaryContext {
+    error: RwSignal<Option<CapturedError>>,
+}
+
+impl ErrorBoundaryContext {
+    /// Creates an error boundary context owned by the current scope.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        ErrorBoundaryContext {
+            error: create_rw_signal(None),
+        }
+    }
+
+    /// Runs `f` in a new scope covered by this boundary, so that errors thrown
+    /// by anything it creates are caught here.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        let (result, _) = create_scope(|| {
+            current_owner().provide_context(*self);
+            f()
+        });
+        result
+    }
+
+    /// Returns the caught error, if any.
+    pub fn error(&self) -> Option<CapturedError> {
+        self.error.get()
+    }
+
+    /// Catches `error`, replacing any error caught before.
+    pub fn throw(&self, error: CapturedError) {
+        self.error.set(Some(error));
+    }
+
+    /// Clears the caught error, so the boundary renders its children again.
+    pub fn reset(&self) {
+        self.error.set(None);
+    }
+}
+
+/// Surfaces an error to the nearest error boundary.
+///
+/// # Panics
+///
+/// Panics if the current scope is not covered by an error boundary.
+pub fn throw_error(error: impl Into<Box<dyn Error>>) {
+    let error = CapturedError::new(error);
+    match current_owner().use_context::<ErrorBoundaryContext>() {
+        Some(boundary) => boundary.throw(error),
+        None => panic!("Uncaught error (no error boundary in scope): {}", error),
+    }
+}
@@ -0,0 +1,406 @@
+//! Reactive collections that report every mutation as a diff.
+//!
+//! A [`SignalVec`] or [`SignalMap`] can be read like a signal, but it also
+//! tells listeners registered with `on_diff` exactly what changed. Renderers
+//! use the diffs to patch the page instead of rebuilding the whole list, so
+//! appending to a long list costs a single DOM insertion.
+
+use crate::signal::RwSignal;
+use crate::{batch, create_rw_signal, on_cleanup, untrack};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+type Listener<D> = Rc<dyn Fn(&D)>;
+
+// Diff listeners, stored in the arena so collections can be `Copy`.
+struct Listeners<D> {
+    next_id: usize,
+    listeners: Vec<(usize, Listener<D>)>,
+}
+
+impl<D> Default for Listeners<D> {
+    fn default() -> Self {
+        Listeners {
+            next_id: 0,
+            listeners: Vec::new(),
+        }
+    }
+}
+
+// Registers `f` for as long as the current scope lives.
+fn add_listener<D: 'static>(listeners: RwSignal<Listeners<D>>, f: impl Fn(&D) + 'static) {
+    let mut id = 0;
+    listeners.update(|l| {
+        id = l.next_id;
+        l.next_id += 1;
+        l.listeners.push((id, Rc::new(f)));
+    });
+    on_cleanup(move || listeners.update(|l| l.listeners.retain(|(i, _)| *i != id)));
+}
+
+// Calls every listener. They are collected first, so a listener may mutate
+// the collection again or register new listeners. Mutations emit inside the
+// batch that updates the collection, so listeners see every diff before
+// effects reading the collection run.
+fn emit<D: 'static>(listeners: RwSignal<Listeners<D>>, diff: D) {
+    let current: Vec<_> =
+        untrack(|| listeners.with(|l| l.listeners.iter().map(|(_, f)| Rc::clone(f)).collect()));
+    for listener in current {
+        listener(&diff);
+    }
+}
+
+/// A single change to a [`SignalVec`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum VecDiff<T> {
+    /// Every item was replaced.
+    Replace { values: Vec<T> },
+    /// An item was inserted at `index`.
+    Insert { index: usize, value: T },
+    /// The item at `index` was replaced.
+    Set { index: usize, value: T },
+    /// The item at `index` was removed.
+    Remove { index: usize },
+    /// The item at `from` was removed and inserted again at `to`.
+    Move { from: usize, to: usize },
+    /// An item was appended.
+    Push { value: T },
+    /// The last item was removed.
+    Pop,
+    /// Every item was removed.
+    Clear,
+}
+
+impl<T: Clone> VecDiff<T> {
+    /// Applies the change to `vec`, which keeps a copy of a `SignalVec` in sync.
+    pub fn apply(&self, vec: &mut Vec<T>) {
+        match self {
+            VecDiff::Replace { values } => *vec = values.clone(),
+            VecDiff::Insert { index, value } => vec.insert(*index, value.clone()),
+            VecDiff::Set { index, value } => vec[*index] = value.clone(),
+            VecDiff::Remove { index } => {
+                vec.remove(*index);
+            }
+            VecDiff::Move { from, to } => {
+                let value = vec.remove(*from);
+                vec.insert(*to, value);
+            }
+            VecDiff::Push { value } => vec.push(value.clone()),
+            VecDiff::Pop => {
+                vec.pop();
+            }
+            VecDiff::Clear => vec.clear(),
+        }
+    }
+}
+
+/// A reactive `Vec` whose mutations are reported as [`VecDiff`]s.
+///
+/// Reading it subscribes the current effect like a signal does. Mutating it
+/// updates the items in place, without cloning the whole vector, notifies
+/// those effects and passes a diff to every `on_diff` listener. Like signal
+/// handles, it is `Copy` and belongs to the scope it was created in.
+pub struct SignalVec<T> {
+    values: RwSignal<Vec<T>>,
+    listeners: RwSignal<Listeners<VecDiff<T>>>,
+}
+
+/// Creates a [`SignalVec`] holding `values`.
+pub fn create_signal_vec<T: Clone + 'static>(values: Vec<T>) -> SignalVec<T> {
+    SignalVec {
+        values: create_rw_signal(values),
+        listeners: create_rw_signal(Listeners::default()),
+    }
+}
+
+impl<T: Clone + 'static> SignalVec<T> {
+    /// Calls `f` with the items, subscribing the current effect.
+    pub fn with<R>(&self, f: impl FnOnce(&[T]) -> R) -> R {
+        self.values.with(|values| f(values))
+    }
+
+    /// Returns a clone of every item, subscribing the current effect.
+    pub fn get(&self) -> Vec<T> {
+        self.values.get()
+    }
+
+    /// Returns a clone of the item at `index`, subscribing the current effect.
+    pub fn get_at(&self, index: usize) -> Option<T> {
+        self.values.with(|values| values.get(index).cloned())
+    }
+
+    /// Returns the number of items, subscribing the current effect.
+    pub fn len(&self) -> usize {
+        self.values.with(Vec::len)
+    }
+
+    /// Returns `true` if there are no items, subscribing the current effect.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends an item.
+    pub fn push(&self, value: T) {
+        batch(|| {
+            self.values.update(|values| values.push(value.clone()));
+            emit(self.listeners, VecDiff::Push { value });
+        })
+    }
+
+    /// Removes the last item and returns it.
+    pub fn pop(&self) -> Option<T> {
+        batch(|| {
+            let mut popped = None;
+            self.values.update(|values| popped = values.pop());
+            if popped.is_some() {
+                emit(self.listeners, VecDiff::Pop);
+            }
+            popped
+        })
+    }
+
+    /// Inserts an item at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the length.
+    pub fn insert(&self, index: usize, value: T) {
+        batch(|| {
+            self.values
+                .update(|values| values.insert(index, value.clone()));
+            emit(self.listeners, VecDiff::Insert { index, value });
+        })
+    }
+
+    /// Removes the item at `index` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        batch(|| {
+            let mut removed = None;
+            self.values
+                .update(|values| removed = Some(values.remove(index)));
+            emit(self.listeners, VecDiff::Remove { index });
+            removed.expect("the item has been removed")
+        })
+    }
+
+    /// Replaces the item at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set(&self, index: usize, value: T) {
+        batch(|| {
+            self.values.update(|values| values[index] = value.clone());
+            emit(self.listeners, VecDiff::Set { index, value });
+        })
+    }
+
+    /// Moves the item at `from` so that it ends up at index `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn move_item(&self, from: usize, to: usize) {
+        batch(|| {
+            self.values.update(|values| {
+                let value = values.remove(from);
+                values.insert(to, value);
+            });
+            emit(self.listeners, VecDiff::Move { from, to });
+        })
+    }
+
+    /// Removes every item.
+    pub fn clear(&self) {
+        batch(|| {
+            self.values.update(Vec::clear);
+            emit(self.listeners, VecDiff::Clear);
+        })
+    }
+
+    /// Replaces every item.
+    pub fn replace(&self, values: Vec<T>) {
+        batch(|| {
+            self.values.set(values.clone());
+            emit(self.listeners, VecDiff::Replace { values });
+        })
+    }
+
+    /// Calls `f` with every change made from now on, for as long as the
+    /// current scope lives.
+    pub fn on_diff(&self, f: impl Fn(&VecDiff<T>) + 'static) {
+        add_listener(self.listeners, f);
+    }
+}
+
+/// A single change to a [`SignalMap`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapDiff<K, V> {
+    /// Every entry was replaced.
+    Replace { entries: BTreeMap<K, V> },
+    /// An entry was added for a key that was not in the map.
+    Insert { key: K, value: V },
+    /// The value of an existing key was replaced.
+    Update { key: K, value: V },
+    /// The entry for `key` was removed.
+    Remove { key: K },
+    /// Every entry was removed.
+    Clear,
+}
+
+impl<K: Ord + Clone, V: Clone> MapDiff<K, V> {
+    /// Applies the change to `map`, which keeps a copy of a `SignalMap` in sync.
+    pub fn apply(&self, map: &mut BTreeMap<K, V>) {
+        match self {
+            MapDiff::Replace { entries } => *map = entries.clone(),
+            MapDiff::Insert { key, value } | MapDiff::Update { key, value } => {
+                map.insert(key.clone(), value.clone());
+            }
+            MapDiff::Remove { key } => {
+                map.remove(key);
+            }
+            MapDiff::Clear => map.clear(),
+        }
+    }
+}
+
+/// A reactive map, ordered by key, whose mutations are reported as
+/// [`MapDiff`]s.
+///
+/// See [`SignalVec`]; the same rules apply.
+pub struct SignalMap<K, V> {
+    entries: RwSignal<BTreeMap<K, V>>,
+    listeners: RwSignal<Listeners<MapDiff<K, V>>>,
+}
+
+/// Creates a [`SignalMap`] holding `entries`.
+pub fn create_signal_map<K, V>(entries: BTreeMap<K, V>) -> SignalMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+    SignalMap {
+        entries: create_rw_signal(entries),
+        listeners: create_rw_signal(Listeners::default()),
+    }
+}
+
+impl<K, V> SignalMap<K, V>
+where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+    /// Calls `f` with the entries, subscribing the current effect.
+    pub fn with<R>(&self, f: impl FnOnce(&BTreeMap<K, V>) -> R) -> R {
+        self.entries.with(f)
+    }
+
+    /// Returns a clone of the value for `key`, subscribing the current effect.
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.entries.with(|entries| entries.get(key).cloned())
+    }
+
+    /// Returns `true` if the map has an entry for `key`, subscribing the
+    /// current effect.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.with(|entries| entries.contains_key(key))
+    }
+
+    /// Returns the number of entries, subscribing the current effect.
+    pub fn len(&self) -> usize {
+        self.entries.with(BTreeMap::len)
+    }
+
+    /// Returns `true` if there are no entries, subscribing the current effect.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the value for `key`, returning the previous value.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        batch(|| {
+            let mut previous = None;
+            self.entries
+                .update(|entries| previous = entries.insert(key.clone(), value.clone()));
+            let diff = if previous.is_some() {
+                MapDiff::Update { key, value }
+            } else {
+                MapDiff::Insert { key, value }
+            };
+            emit(self.listeners, diff);
+            previous
+        })
+    }
+
+    /// Removes the entry for `key`, returning its value.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        batch(|| {
+            let mut removed = None;
+            self.entries.update(|entries| removed = entries.remove(key));
+            if removed.is_some() {
+                emit(self.listeners, MapDiff::Remove { key: key.clone() });
+            }
+            removed
+        })
+    }
+
+    /// Removes every entry.
+    pub fn clear(&self) {
+        batch(|| {
+            self.entries.update(BTreeMap::clear);
+            emit(self.listeners, MapDiff::Clear);
+        })
+    }
+
+    /// Replaces every entry.
+    pub fn replace(&self, entries: BTreeMap<K, V>) {
+        batch(|| {
+            self.entries.set(entries.clone());
+            emit(self.listeners, MapDiff::Replace { entries });
+        })
+    }
+
+    /// Calls `f` with every change made from now on, for as long as the
+    /// current scope lives.
+    pub fn on_diff(&self, f: impl Fn(&MapDiff<K, V>) + 'static) {
+        add_listener(self.listeners, f);
+    }
+}
+
+// Implemented by hand so that collections are `Copy` whatever they hold.
+impl<T> Clone for SignalVec<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SignalVec<T> {}
+
+impl<T> fmt::Debug for SignalVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalVec")
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl<K, V> Clone for SignalMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for SignalMap<K, V> {}
+
+impl<K, V> fmt::Debug for SignalMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalMap")
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
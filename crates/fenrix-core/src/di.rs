@@ -0,0 +1,437 @@
+//! Dependency injection.
+//!
+//! Services live in a tree of injectors that mirrors the ownership tree.
+//! [`provide_context`] registers a service in the current scope, where it is
+//! visible to everything created in that scope and its descendants and shadows
+//! any provider of the same type further up. The global container filled by
+//! [`provide_service`] is the root of the tree, so it is consulted last.
+
+use crate::owner::{current_owner, Owner};
+use crate::{create_root, on_cleanup, untrack};
+use std::any::{type_name, Any, TypeId};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+// A service, with the name of its type for error messages.
+struct Service {
+    type_name: &'static str,
+    provider: Rc<Provider>,
+}
+
+// How a service is obtained. Every `dyn Any` here holds an `Rc<T>`, so
+// unsized services like trait objects can be stored as well.
+enum Provider {
+    Instance(Rc<dyn Any>),
+    Factory {
+        lifetime: Lifetime,
+        create: Box<dyn Fn() -> Rc<dyn Any>>,
+        instance: RefCell<Option<Rc<dyn Any>>>,
+    },
+}
+
+// How often a factory is called: once, on first injection, or for every
+// injection.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Lifetime {
+    Singleton,
+    Transient,
+}
+
+// A container for dependency-injected services.
+#[derive(Default)]
+pub struct ServiceContainer {
+    services: HashMap<TypeId, Service>,
+}
+
+impl ServiceContainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Registers `provider` for `T`, returning the provider it replaces.
+    fn insert<T: ?Sized + 'static>(&mut self, provider: Provider) -> Option<Service> {
+        let service = Service {
+            type_name: type_name::<T>(),
+            provider: Rc::new(provider),
+        };
+        self.services.insert(TypeId::of::<T>(), service)
+    }
+
+    fn provider<T: ?Sized + 'static>(&self) -> Option<Rc<Provider>> {
+        let service = self.services.get(&TypeId::of::<T>())?;
+        Some(Rc::clone(&service.provider))
+    }
+
+    fn type_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.services.values().map(|service| service.type_name)
+    }
+}
+
+// The services provided to a single scope, stored as one of its contexts.
+type Injector = Rc<RefCell<ServiceContainer>>;
+
+thread_local! {
+    // A thread-local static container for services. It is only ever replaced
+    // by `with_test_container`.
+    static SERVICE_CONTAINER: RefCell<Injector> = RefCell::default();
+    // Services replaced by `override_service`, which win over every provider.
+    static OVERRIDES: RefCell<Injector> = RefCell::default();
+    // The services whose factories are running, outermost first.
+    static RESOLVING: RefCell<Vec<(TypeId, &'static str)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn global_container() -> Injector {
+    SERVICE_CONTAINER.with(|sc| Rc::clone(&sc.borrow()))
+}
+
+fn overrides() -> Injector {
+    OVERRIDES.with(|overrides| Rc::clone(&overrides.borrow()))
+}
+
+// Returns every injector in reach of the current scope, in lookup order:
+// overrides, the current scope and its ancestors, then the global container.
+fn injectors() -> Vec<Injector> {
+    let mut injectors = vec![overrides()];
+    let mut owner = Some(current_owner());
+    while let Some(current) = owner {
+        injectors.extend(current.local_context::<Injector>());
+        owner = current.parent();
+    }
+    injectors.push(global_container());
+    injectors
+}
+
+// Returns `true` if a service is registered under `id` where a singleton
+// factory can find it, without constructing it. Factories run in a root of
+// their own, so only overrides and the global container count, not the
+// providers of the current scope.
+pub(crate) fn is_provided(id: TypeId) -> bool {
+    [overrides(), global_container()]
+        .iter()
+        .any(|injector| injector.borrow().services.contains_key(&id))
+}
+
+// Finds the closest provider of `T` and gets the service from it. No
+// container is borrowed while a factory runs, since it may inject or
+// provide services itself.
+fn find<T: ?Sized + 'static>() -> Result<Option<Rc<T>>, DiError> {
+    let provider = injectors()
+        .iter()
+        .find_map(|injector| injector.borrow().provider::<T>());
+    match provider {
+        Some(provider) => resolve::<T>(&provider).map(Some),
+        None => Ok(None),
+    }
+}
+
+fn resolve<T: ?Sized + 'static>(provider: &Provider) -> Result<Rc<T>, DiError> {
+    let value = match provider {
+        Provider::Instance(value) => Rc::clone(value),
+        Provider::Factory {
+            lifetime,
+            create,
+            instance,
+        } => {
+            if let Some(value) = instance.borrow().as_ref() {
+                return Ok(downcast(value));
+            }
+            let value = {
+                let _guard = ResolveGuard::enter::<T>()?;
+                create()
+            };
+            if *lifetime == Lifetime::Singleton {
+                *instance.borrow_mut() = Some(Rc::clone(&value));
+            }
+            value
+        }
+    };
+    Ok(downcast(&value))
+}
+
+fn downcast<T: ?Sized + 'static>(value: &Rc<dyn Any>) -> Rc<T> {
+    let service = value
+        .downcast_ref::<Rc<T>>()
+        .expect("services are stored under their own type");
+    Rc::clone(service)
+}
+
+// Marks `T` as being resolved until dropped, failing if it already is.
+struct ResolveGuard;
+
+impl ResolveGuard {
+    fn enter<T: ?Sized + 'static>() -> Result<ResolveGuard, DiError> {
+        let id = TypeId::of::<T>();
+        RESOLVING.with(|resolving| {
+            let mut resolving = resolving.borrow_mut();
+            if let Some(start) = resolving.iter().position(|(other, _)| *other == id) {
+                let mut path: Vec<_> = resolving[start..].iter().map(|(_, name)| *name).collect();
+                path.push(type_name::<T>());
+                return Err(DiError::Cycle { path });
+            }
+            resolving.push((id, type_name::<T>()));
+            Ok(ResolveGuard)
+        })
+    }
+}
+
+impl Drop for ResolveGuard {
+    fn drop(&mut self) {
+        RESOLVING.with(|resolving| resolving.borrow_mut().pop());
+    }
+}
+
+/// An error returned when a service cannot be injected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiError {
+    /// No provider of the service is in reach of the current scope.
+    NotProvided {
+        /// The requested type, as returned by [`std::any::type_name`].
+        type_name: &'static str,
+        /// The types of all services in reach, sorted by name.
+        registered: Vec<&'static str>,
+    },
+    /// The factory of a service needs the service itself, directly or through
+    /// other factories.
+    Cycle {
+        /// The services being constructed, starting and ending with the same
+        /// type.
+        path: Vec<&'static str>,
+    },
+    /// A service registered by a [`Module`](crate::Module) depends on a
+    /// service that neither the module nor the container provides.
+    MissingDependency {
+        /// The service being registered.
+        service: &'static str,
+        /// The dependency that is not provided.
+        dependency: &'static str,
+    },
+}
+
+impl fmt::Display for DiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiError::NotProvided {
+                type_name,
+                registered,
+            } => {
+                write!(
+                    f,
+                    "Service not provided: `{}`. Make sure to call `provide_service` or \
+                     `provide_context` before `inject`. ",
+                    type_name
+                )?;
+                if registered.is_empty() {
+                    write!(f, "No services are registered.")
+                } else {
+                    write!(f, "Registered services: `{}`.", registered.join("`, `"))
+                }
+            }
+            DiError::Cycle { path } => {
+                write!(f, "Dependency cycle detected: `{}`.", path.join("` -> `"))
+            }
+            DiError::MissingDependency {
+                service,
+                dependency,
+            } => write!(
+                f,
+                "`{}` depends on `{}`, which is not provided. Add it to the module or \
+                 provide it before the module.",
+                service, dependency
+            ),
+        }
+    }
+}
+
+impl Error for DiError {}
+
+/// Provides a service to the application's global DI container.
+///
+/// The service is stored by its type, so only one instance of any
+/// given type can be provided.
+pub fn provide_service<T: 'static>(service: T) {
+    provide_service_as::<T>(Rc::new(service));
+}
+
+/// Provides a shared service to the global DI container under the type `T`,
+/// which may be a trait object.
+///
+/// Components can then depend on the trait instead of an implementation:
+///
+/// ```ignore
+/// provide_service_as::<dyn Logger>(Rc::new(ConsoleLogger));
+/// let logger: Rc<dyn Logger> = inject::<dyn Logger>();
+/// ```
+pub fn provide_service_as<T: ?Sized + 'static>(service: Rc<T>) {
+    let provider = Provider::Instance(Rc::new(service));
+    global_container().borrow_mut().insert::<T>(provider);
+}
+
+/// Registers a factory that creates the service the first time it is
+/// injected. Every later injection gets the same instance.
+///
+/// The factory may `inject` the services it depends on; a factory that ends
+/// up needing its own service fails with [`DiError::Cycle`]. It runs in a
+/// root scope of its own, so it only sees global services and what it
+/// creates lives as long as the application.
+pub fn provide_factory<T: ?Sized + 'static>(factory: impl Fn() -> Rc<T> + 'static) {
+    let create = move || create_root(|| untrack(&factory)).0;
+    insert_factory::<T>(Lifetime::Singleton, create);
+}
+
+/// Registers a factory that creates a new instance of the service every
+/// time it is injected.
+///
+/// Unlike [`provide_factory`], the factory runs in the scope that injects
+/// the service, so it sees that scope's providers and what it creates is
+/// owned by it.
+pub fn provide_transient<T: ?Sized + 'static>(factory: impl Fn() -> Rc<T> + 'static) {
+    let create = move || untrack(&factory);
+    insert_factory::<T>(Lifetime::Transient, create);
+}
+
+fn insert_factory<T: ?Sized + 'static>(lifetime: Lifetime, create: impl Fn() -> Rc<T> + 'static) {
+    let provider = Provider::Factory {
+        lifetime,
+        create: Box::new(move || Rc::new(create()) as Rc<dyn Any>),
+        instance: RefCell::default(),
+    };
+    global_container().borrow_mut().insert::<T>(provider);
+}
+
+/// Provides a service to the current scope and everything created in it.
+///
+/// Called while a component renders, this makes `value` available to the
+/// component and all of its children, shadowing a service of the same type
+/// provided further up or in the global container. The service is dropped
+/// when the scope is disposed.
+pub fn provide_context<T: 'static>(value: T) {
+    let owner = current_owner();
+    let provider = Provider::Instance(Rc::new(Rc::new(value)));
+    injector_of(&owner).borrow_mut().insert::<T>(provider);
+}
+
+// Returns the injector of `owner`, creating it on first use.
+fn injector_of(owner: &Owner) -> Injector {
+    owner.local_context::<Injector>().unwrap_or_else(|| {
+        let injector = Injector::default();
+        owner.provide_context(Rc::clone(&injector));
+        injector
+    })
+}
+
+/// Looks a service up in the current scope, then in each enclosing scope and
+/// finally in the global container, returning the closest one.
+///
+/// Returns `None` if no provider of `T` is in reach. Like other hooks, call
+/// it while the component renders rather than from an event handler, which
+/// runs outside of the component's scope.
+///
+/// # Panics
+///
+/// Panics if the service's factory depends on itself.
+pub fn use_context<T: ?Sized + 'static>() -> Option<Rc<T>> {
+    inject_optional::<T>()
+}
+
+/// Injects a service like [`inject`], but returns an error naming the
+/// missing type instead of panicking.
+pub fn try_inject<T: ?Sized + 'static>() -> Result<Rc<T>, DiError> {
+    find::<T>()?.ok_or_else(|| {
+        let mut registered = BTreeSet::new();
+        for injector in injectors() {
+            registered.extend(injector.borrow().type_names());
+        }
+        DiError::NotProvided {
+            type_name: type_name::<T>(),
+            registered: registered.into_iter().collect(),
+        }
+    })
+}
+
+/// Injects a service like [`inject`], returning `None` if it has not been
+/// provided. Use it for services a component can do without.
+///
+/// # Panics
+///
+/// Panics if the service's factory depends on itself.
+pub fn inject_optional<T: ?Sized + 'static>() -> Option<Rc<T>> {
+    find::<T>().unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Injects a service from the closest provider, falling back to the global
+/// DI container.
+///
+/// This hook retrieves a shared reference (`Rc`) to a service that was
+/// previously provided via `provide_context` or `provide_service`. See
+/// [`use_context`] for the lookup order.
+///
+/// # Panics
+///
+/// This function will panic if the requested service (`T`) has not been
+/// provided, or if its factory depends on itself. The message names `T` and
+/// every registered service; use [`try_inject`] to handle the error instead.
+pub fn inject<T: ?Sized + 'static>() -> Rc<T> {
+    try_inject::<T>().unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Replaces the service `T` with `service` for as long as the current scope
+/// lives, wherever it is injected from.
+///
+/// An override wins over every provider, including scoped ones and the ones
+/// factories would otherwise see, so a test can swap an API client for a
+/// mock underneath the components and services that use it. Singletons that
+/// were already built keep the instance they were built with.
+pub fn override_service<T: 'static>(service: T) {
+    override_service_as::<T>(Rc::new(service));
+}
+
+/// Like [`override_service`], for a service registered under a trait object
+/// with [`provide_service_as`].
+pub fn override_service_as<T: ?Sized + 'static>(service: Rc<T>) {
+    let overrides = overrides();
+    let previous = overrides
+        .borrow_mut()
+        .insert::<T>(Provider::Instance(Rc::new(service)));
+    // Nested overrides of the same type are undone in reverse order.
+    on_cleanup(move || {
+        let mut overrides = overrides.borrow_mut();
+        match previous {
+            Some(previous) => overrides.services.insert(TypeId::of::<T>(), previous),
+            None => overrides.services.remove(&TypeId::of::<T>()),
+        };
+    });
+}
+
+/// Runs `f` against a fresh, empty DI container, restoring the previous one
+/// afterwards.
+///
+/// `f` runs in a root scope of its own that is disposed when it returns, so
+/// nothing it provides or overrides outlives it. Tests sharing a thread do
+/// not see each other's services.
+pub fn with_test_container<R>(f: impl FnOnce() -> R) -> R {
+    let _restore = RestoreContainers {
+        global: SERVICE_CONTAINER.with(|sc| sc.replace(Injector::default())),
+        overrides: OVERRIDES.with(|overrides| overrides.replace(Injector::default())),
+    };
+    let (result, root) = create_root(f);
+    root.dispose();
+    result
+}
+
+// Puts the containers replaced by `with_test_container` back, even if the
+// test panics.
+struct RestoreContainers {
+    global: Injector,
+    overrides: Injector,
+}
+
+impl Drop for RestoreContainers {
+    fn drop(&mut self) {
+        SERVICE_CONTAINER.with(|sc| *sc.borrow_mut() = Rc::clone(&self.global));
+        OVERRIDES.with(|overrides| *overrides.borrow_mut() = Rc::clone(&self.overrides));
+    }
+}
@@ -0,0 +1,123 @@
+//! A minimal single-threaded executor for the futures behind resources.
+//!
+//! Tasks are polled on the thread that spawned them, as soon as they are
+//! spawned and then whenever they are woken. This needs no runtime: in the
+//! browser the wakers are called by the promises the futures are waiting on,
+//! and in native tests by whatever the test uses to complete them.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+// Ids of the tasks that have been woken and need to be polled again. It is
+// shared with the wakers, which may be sent to other threads.
+type ReadyQueue = Arc<Mutex<VecDeque<usize>>>;
+
+#[derive(Default)]
+struct Executor {
+    // A task is `None` while it is being polled. Aborting a task removes its
+    // entry, so a task aborted from inside its own poll is not put back.
+    tasks: RefCell<HashMap<usize, Option<LocalTask>>>,
+    ready: ReadyQueue,
+    next_id: Cell<usize>,
+    running: Cell<bool>,
+}
+
+thread_local! {
+    static EXECUTOR: Executor = Executor::default();
+}
+
+// Wakes a task by queuing its id on the executor that spawned it.
+struct TaskWaker {
+    id: usize,
+    ready: ReadyQueue,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.id);
+        // Only the thread that owns the tasks can poll them. A wake from any
+        // other thread is picked up the next time this executor runs.
+        let _ = EXECUTOR.try_with(|executor| {
+            if Arc::ptr_eq(&executor.ready, &self.ready) {
+                executor.run();
+            }
+        });
+    }
+}
+
+/// A handle to a task started by [`spawn_local`].
+pub(crate) struct Task {
+    id: usize,
+}
+
+impl Task {
+    /// Drops the task's future without polling it again. Aborting a task
+    /// that has already finished does nothing.
+    pub(crate) fn abort(&self) {
+        let task = EXECUTOR.with(|executor| executor.tasks.borrow_mut().remove(&self.id));
+        drop(task);
+    }
+}
+
+/// Starts polling `future` on the current thread.
+pub(crate) fn spawn_local(future: impl Future<Output = ()> + 'static) -> Task {
+    EXECUTOR.with(|executor| {
+        let id = executor.next_id.get();
+        executor.next_id.set(id + 1);
+        executor
+            .tasks
+            .borrow_mut()
+            .insert(id, Some(Box::pin(future)));
+        executor.ready.lock().unwrap().push_back(id);
+        executor.run();
+        Task { id }
+    })
+}
+
+impl Executor {
+    // Polls ready tasks until none are left. Tasks spawned or woken while
+    // this is running are picked up by the same loop.
+    fn run(&self) {
+        if self.running.replace(true) {
+            return;
+        }
+        loop {
+            let Some(id) = self.ready.lock().unwrap().pop_front() else {
+                break;
+            };
+            // Skips tasks that have finished or been aborted since they were woken.
+            let Some(mut task) = self.tasks.borrow_mut().get_mut(&id).and_then(Option::take) else {
+                continue;
+            };
+            let waker = Waker::from(Arc::new(TaskWaker {
+                id,
+                ready: Arc::clone(&self.ready),
+            }));
+            let mut cx = Context::from_waker(&waker);
+            // Reads made while polling belong to no effect in particular.
+            let poll = crate::untrack(|| task.as_mut().poll(&mut cx));
+            let mut tasks = self.tasks.borrow_mut();
+            match (poll, tasks.get_mut(&id)) {
+                (Poll::Pending, Some(slot)) => *slot = Some(task),
+                _ => {
+                    tasks.remove(&id);
+                    // The future is dropped outside of the borrow, in case
+                    // its destructor aborts other tasks.
+                    drop(tasks);
+                    drop(task);
+                }
+            }
+        }
+        self.running.set(false);
+    }
+}
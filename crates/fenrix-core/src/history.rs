@@ -0,0 +1,163 @@
+//! Signals that remember their previous values for undo and redo.
+
+use crate::signal::RwSignal;
+use crate::{batch, create_rw_signal, current_batch, untrack};
+use std::collections::VecDeque;
+use std::fmt;
+
+struct History<T> {
+    past: VecDeque<T>,
+    future: Vec<T>,
+    capacity: usize,
+    // The batch that recorded the last entry. Later writes in the same batch
+    // are part of that entry.
+    last_batch: Option<usize>,
+}
+
+/// A signal that records every change so it can be undone and redone.
+///
+/// Reading it subscribes the current effect like a signal does, and
+/// [`can_undo`](Self::can_undo) and [`can_redo`](Self::can_redo) are reactive
+/// too, so an "Undo" button can be disabled by an effect. Like signal
+/// handles, it is `Copy` and belongs to the scope it was created in.
+pub struct HistorySignal<T> {
+    value: RwSignal<T>,
+    history: RwSignal<History<T>>,
+}
+
+/// Creates a [`HistorySignal`] holding `initial` that remembers up to
+/// `capacity` previous values; the oldest are forgotten first.
+///
+/// Every `set` or `update` is one history entry, except that all the writes
+/// made inside one [`batch`] form a single entry.
+pub fn create_history_signal<T: Clone + 'static>(initial: T, capacity: usize) -> HistorySignal<T> {
+    HistorySignal {
+        value: create_rw_signal(initial),
+        history: create_rw_signal(History {
+            past: VecDeque::new(),
+            future: Vec::new(),
+            capacity,
+            last_batch: None,
+        }),
+    }
+}
+
+impl<T: Clone + 'static> HistorySignal<T> {
+    /// Returns a clone of the current value, subscribing the current effect.
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    /// Calls `f` with the current value, subscribing the current effect.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.value.with(f)
+    }
+
+    /// Replaces the value, recording the previous one. Anything that was
+    /// undone can no longer be redone.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Mutates the value in place, recording the previous one. Anything that
+    /// was undone can no longer be redone.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        batch(|| {
+            self.record();
+            self.value.update(f);
+        });
+    }
+
+    // Saves the current value as a history entry, unless this batch already
+    // did.
+    fn record(&self) {
+        let batch = current_batch();
+        let grouped = untrack(|| {
+            self.history
+                .with(|h| h.last_batch.is_some() && h.last_batch == batch)
+        });
+        if grouped {
+            return;
+        }
+        let current = untrack(|| self.value.get());
+        self.history.update(|h| {
+            h.future.clear();
+            h.past.push_back(current);
+            if h.past.len() > h.capacity {
+                h.past.pop_front();
+            }
+            h.last_batch = batch;
+        });
+    }
+
+    /// Restores the previous value. Returns `false` if there is nothing to
+    /// undo.
+    pub fn undo(&self) -> bool {
+        if !untrack(|| self.can_undo()) {
+            return false;
+        }
+        self.step(|h, current| {
+            h.future.push(current);
+            h.past.pop_back()
+        });
+        true
+    }
+
+    /// Restores the value that was last undone. Returns `false` if there is
+    /// nothing to redo.
+    pub fn redo(&self) -> bool {
+        if !untrack(|| self.can_redo()) {
+            return false;
+        }
+        self.step(|h, current| {
+            h.past.push_back(current);
+            h.future.pop()
+        });
+        true
+    }
+
+    // Saves the current value on one side of the history and restores the
+    // value `swap` takes from the other side.
+    fn step(&self, swap: impl FnOnce(&mut History<T>, T) -> Option<T>) {
+        let current = untrack(|| self.value.get());
+        batch(|| {
+            let mut next = None;
+            self.history.update(|h| {
+                next = swap(h, current);
+                // A write after this starts a new entry.
+                h.last_batch = None;
+            });
+            self.value
+                .set(next.expect("the history has an entry to restore"));
+        });
+    }
+
+    /// Returns `true` if there is a value to undo to, subscribing the current
+    /// effect.
+    pub fn can_undo(&self) -> bool {
+        self.history.with(|h| !h.past.is_empty())
+    }
+
+    /// Returns `true` if there is a value to redo, subscribing the current
+    /// effect.
+    pub fn can_redo(&self) -> bool {
+        self.history.with(|h| !h.future.is_empty())
+    }
+}
+
+// Implemented by hand so that history signals are `Copy` whatever they hold.
+impl<T> Clone for HistorySignal<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for HistorySignal<T> {}
+
+impl<T> fmt::Debug for HistorySignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistorySignal")
+            .field("value", &self.value)
+            .finish()
+    }
+}
@@ -0,0 +1,365 @@
+//! Hook slots and the component instances that own them.
+//!
+//! Every hook call takes the next slot of the component that is rendering.
+//! Slots are matched to hook calls by position, so a component must call the
+//! same hooks in the same order on every render. Debug builds record where
+//! each hook was called and report a hook that moved, naming the component
+//! and both call sites. Release builds skip the bookkeeping and only notice a
+//! slot holding a value of the wrong type.
+
+use crate::{create_scope, untrack, Scope};
+use std::any::Any;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt;
+use std::panic::Location;
+use std::rc::Rc;
+
+/// The name used for components rendered without one.
+const ANONYMOUS: &str = "<anonymous component>";
+
+/// Where a hook was called.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HookSite {
+    /// The name of the hook, such as `use_state`.
+    pub hook: &'static str,
+    /// The source location of the call.
+    pub location: &'static Location<'static>,
+}
+
+impl fmt::Display for HookSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` at {}", self.hook, self.location)
+    }
+}
+
+/// A violation of the rules of hooks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HookError {
+    /// A hook was called outside of any component.
+    OutsideComponent { hook: &'static str },
+    /// The hook at `index` was called from a different place than in the
+    /// first render. Only detected in debug builds.
+    OrderChanged {
+        component: &'static str,
+        index: usize,
+        expected: HookSite,
+        found: HookSite,
+    },
+    /// The component called a different number of hooks than in the first
+    /// render. Only detected in debug builds.
+    CountChanged {
+        component: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The slot at `index` holds a value of another type than the hook
+    /// expects. Debug builds report [`HookError::OrderChanged`] instead.
+    TypeMismatch {
+        component: &'static str,
+        index: usize,
+        hook: &'static str,
+    },
+}
+
+impl fmt::Display for HookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookError::OutsideComponent { hook } => {
+                write!(f, "`{}` can only be called inside a component.", hook)
+            }
+            HookError::OrderChanged {
+                component,
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Hook order changed in `{}`: hook #{} was {} in the first render, but is {} now. \
+                 Hooks must be called in the same order on every render, so do not call them \
+                 conditionally.",
+                component, index, expected, found
+            ),
+            HookError::CountChanged {
+                component,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` called {} hooks, but called {} in the first render. Hooks must be called \
+                 in the same order on every render, so do not call them conditionally.",
+                component, found, expected
+            ),
+            HookError::TypeMismatch {
+                component,
+                index,
+                hook,
+            } => write!(
+                f,
+                "Mismatched state type in `{}` hook (hook #{} of `{}`).",
+                hook, index, component
+            ),
+        }
+    }
+}
+
+impl Error for HookError {}
+
+// The hook state of a single component instance.
+struct ComponentContext {
+    name: &'static str,
+    slots: Vec<Rc<dyn Any>>,
+    index: usize,
+    // Owns everything hooks create, so it outlives individual renders.
+    scope: Scope,
+    #[cfg(debug_assertions)]
+    sites: Vec<HookSite>,
+    has_rendered: bool,
+    // When set, errors are collected here instead of panicking.
+    collected: Option<Option<HookError>>,
+    // Whether a `#[component]` called first in the render takes over this
+    // context instead of creating an instance of its own.
+    adoptable: bool,
+}
+
+thread_local! {
+    // The components being rendered, innermost last.
+    static CONTEXT_STACK: RefCell<Vec<ComponentContext>> = const { RefCell::new(Vec::new()) };
+}
+
+impl ComponentContext {
+    // Panics with `error`, or records it and forgets the slots from `index`
+    // on, so the rest of the render starts from fresh state.
+    fn fail(&mut self, error: HookError, index: usize) {
+        let Some(collected) = &mut self.collected else {
+            panic!("{}", error);
+        };
+        collected.get_or_insert(error);
+        self.slots.truncate(index);
+        #[cfg(debug_assertions)]
+        self.sites.truncate(index);
+    }
+
+    // Returns the slot for the hook at `index`, or `None` if it has to be
+    // created.
+    fn existing_slot<T: 'static>(
+        &mut self,
+        index: usize,
+        hook: &'static str,
+        location: &'static Location<'static>,
+    ) -> Option<Rc<T>> {
+        #[cfg(debug_assertions)]
+        {
+            let found = HookSite { hook, location };
+            if let Some(&expected) = self.sites.get(index) {
+                if expected != found {
+                    let component = self.name;
+                    self.fail(
+                        HookError::OrderChanged {
+                            component,
+                            index,
+                            expected,
+                            found,
+                        },
+                        index,
+                    );
+                    return None;
+                }
+            }
+        }
+        #[cfg(not(debug_assertions))]
+        let _ = location;
+
+        let slot = Rc::clone(self.slots.get(index)?);
+        match slot.downcast::<T>() {
+            Ok(slot) => Some(slot),
+            Err(_) => {
+                let component = self.name;
+                self.fail(
+                    HookError::TypeMismatch {
+                        component,
+                        index,
+                        hook,
+                    },
+                    index,
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Returns the value in the current component's next hook slot, filling the
+/// slot with `init` the first time the hook is called.
+#[track_caller]
+pub(crate) fn use_hook_slot<T: 'static>(hook: &'static str, init: impl FnOnce() -> T) -> Rc<T> {
+    let location = Location::caller();
+    let (existing, scope) = CONTEXT_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let Some(context) = stack.last_mut() else {
+            panic!("{}", HookError::OutsideComponent { hook });
+        };
+        let index = context.index;
+        context.index += 1;
+        context.adoptable = false;
+        let existing = context.existing_slot::<T>(index, hook, location);
+        (existing, context.scope.clone())
+    });
+    if let Some(slot) = existing {
+        return slot;
+    }
+
+    // `init` runs without the stack borrowed, since it may create signals or
+    // call into user code. What it creates lives as long as the component.
+    let slot = Rc::new(scope.run(init));
+    CONTEXT_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let context = stack.last_mut().expect("the component is still rendering");
+        context.slots.push(Rc::clone(&slot) as Rc<dyn Any>);
+        #[cfg(debug_assertions)]
+        context.sites.push(HookSite { hook, location });
+    });
+    slot
+}
+
+/// A component whose hook state persists across renders.
+///
+/// Each render runs in a new scope, and the scope of the previous render is
+/// disposed. State created by hooks belongs to the instance instead, so a
+/// hook returns the same state on every render as long as the hooks are
+/// called in the same order.
+pub struct ComponentInstance {
+    context: RefCell<Option<ComponentContext>>,
+    render_scope: RefCell<Option<Scope>>,
+}
+
+impl ComponentInstance {
+    /// Creates an instance owned by the current scope. `name` is used in
+    /// error messages.
+    pub fn new(name: &'static str) -> Self {
+        let ((), scope) = create_scope(|| {});
+        ComponentInstance {
+            context: RefCell::new(Some(ComponentContext {
+                name,
+                slots: Vec::new(),
+                index: 0,
+                scope,
+                #[cfg(debug_assertions)]
+                sites: Vec::new(),
+                has_rendered: false,
+                collected: None,
+                adoptable: false,
+            })),
+            render_scope: RefCell::new(None),
+        }
+    }
+
+    /// Renders the component by calling `f`.
+    ///
+    /// When `f` renders a `#[component]` before calling any hook, that
+    /// component renders into this instance rather than into a new one, so
+    /// its hooks keep their state and are checked across renders:
+    /// `instance.render(|| Profile())`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a hook is used differently than in the first render.
+    pub fn render<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.render_with(None, true, f).0
+    }
+
+    /// Renders the component like [`render`](Self::render), but returns the
+    /// first misuse of hooks as an error instead of panicking.
+    ///
+    /// Hooks from the first misused one on start over with fresh state, so
+    /// the next render is compared against this one.
+    pub fn try_render<R>(&self, f: impl FnOnce() -> R) -> Result<R, HookError> {
+        match self.render_with(Some(None), true, f) {
+            (result, None) => Ok(result),
+            (_, Some(error)) => Err(error),
+        }
+    }
+
+    fn render_with<R>(
+        &self,
+        collected: Option<Option<HookError>>,
+        adoptable: bool,
+        f: impl FnOnce() -> R,
+    ) -> (R, Option<HookError>) {
+        let mut context = self
+            .context
+            .borrow_mut()
+            .take()
+            .expect("A component cannot render itself while it is rendering.");
+        context.index = 0;
+        context.collected = collected;
+        context.adoptable = adoptable;
+        let scope = context.scope.clone();
+
+        if let Some(previous) = self.render_scope.borrow_mut().take() {
+            previous.dispose();
+        }
+        CONTEXT_STACK.with(|s| s.borrow_mut().push(context));
+        // The body runs untracked: signals it reads directly must not make
+        // whatever is rendering it re-run; only the effects it creates track
+        // their own reads.
+        let (result, render_scope) = scope.run(|| create_scope(|| untrack(f)));
+        let mut context = CONTEXT_STACK
+            .with(|s| s.borrow_mut().pop())
+            .expect("the component is still on the stack");
+        *self.render_scope.borrow_mut() = Some(render_scope);
+
+        #[cfg(debug_assertions)]
+        if context.has_rendered && context.index != context.slots.len() {
+            let error = HookError::CountChanged {
+                component: context.name,
+                expected: context.slots.len(),
+                found: context.index,
+            };
+            let index = context.index;
+            context.fail(error, index);
+        }
+        context.has_rendered = true;
+        let error = context.collected.take().flatten();
+        *self.context.borrow_mut() = Some(context);
+        (result, error)
+    }
+}
+
+/// A helper function to be called by the `#[component]` macro.
+pub fn with_component_context<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    with_named_component_context(ANONYMOUS, f)
+}
+
+/// Like [`with_component_context`], naming the component in hook errors.
+/// This is what the `#[component]` macro calls.
+///
+/// Each call renders a new instance, since a component function is called
+/// once and then updates through the signals it reads. Hook order is only
+/// checked across renders when the component is rendered again through the
+/// same [`ComponentInstance`].
+pub fn with_named_component_context<F, R>(name: &'static str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let adopted = CONTEXT_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        match stack.last_mut() {
+            Some(context) if context.adoptable => {
+                context.adoptable = false;
+                context.name = name;
+                true
+            }
+            _ => false,
+        }
+    });
+    if adopted {
+        return f();
+    }
+    // Everything the component creates is owned by the instance's scope, so
+    // it is all torn down together when the component is removed.
+    ComponentInstance::new(name).render_with(None, false, f).0
+}
@@ -1,201 +1,385 @@
-use std::any::{Any, TypeId};
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::rc::Rc;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::{Rc, Weak};
+
+mod boundary;
+mod collections;
+mod di;
+mod executor;
+mod history;
+mod hooks;
+mod module;
+mod owner;
+mod persist;
+mod props;
+mod reducer;
+mod resource;
+mod signal;
+mod store;
+
+pub use boundary::{throw_error, CapturedError, ErrorBoundaryContext, SuspenseContext};
+pub use collections::{
+    create_signal_map, create_signal_vec, MapDiff, SignalMap, SignalVec, VecDiff,
+};
+pub use di::{
+    inject, inject_optional, override_service, override_service_as, provide_context,
+    provide_factory, provide_service, provide_service_as, provide_transient, try_inject,
+    use_context, with_test_container, DiError, ServiceContainer,
+};
+pub use history::{create_history_signal, HistorySignal};
+pub use hooks::{
+    with_component_context, with_named_component_context, ComponentInstance, HookError, HookSite,
+};
+pub use module::{Dependency, Injectable, Module};
+pub use owner::{create_root, create_scope, on_cleanup, Scope};
+pub use persist::{
+    create_persisted_signal, create_persisted_signal_in, MemoryStorage, Storage, StorageListener,
+};
+pub use props::{component_props, Component, NoProps, Props, WithProps, WithoutProps};
+pub use reducer::{use_reducer, use_reducer_with_middleware, Middleware};
+pub use resource::{create_resource, Resource};
+pub use signal::{create_rw_signal, ReadSignal, RwSignal, WriteSignal};
+pub use store::{create_store, Store};
+
+use hooks::use_hook_slot;
+use owner::{current_owner, with_owner, Owner};
 
 // The internal state of a signal.
 struct Signal<T> {
     value: T,
-    subscribers: Vec<Rc<dyn Fn()>>,
+    subscribers: Rc<Subscribers>,
 }
 
-type AnySignal = Rc<dyn Any>;
+// The effects currently subscribed to a signal or memo. Effects are held
+// weakly, so an effect that has been dropped simply stops being notified.
+#[derive(Default)]
+struct Subscribers(RefCell<Vec<Weak<Effect>>>);
+
+// A reactive computation that re-runs when the signals it reads change.
+struct Effect {
+    // Effects are numbered in creation order. An effect can only read memos
+    // that already exist, so this is also a valid dependency order.
+    id: usize,
+    // Memos are flushed before plain effects so effects never see stale values.
+    is_memo: bool,
+    run: Box<dyn Fn()>,
+    // The subscriber lists this effect joined during its last run.
+    sources: RefCell<Vec<Rc<Subscribers>>>,
+    // Owns everything created during the last run. It is cleaned before the
+    // effect runs again.
+    owner: Rc<Owner>,
+}
 
-// The context for a single component instance, managing its hooks.
+// Queues effects invalidated by signal writes until the outermost batch ends.
 #[derive(Default)]
-struct ComponentContext {
-    states: Vec<AnySignal>,
-    effects: Vec<()>, // Use a unit type to simply mark that an effect has been created.
-    state_index: usize,
-    effect_index: usize,
+struct Scheduler {
+    batch_depth: usize,
+    // Numbers the outermost batches, so writes can tell whether they belong
+    // to the same one.
+    batch_id: usize,
+    flushing: bool,
+    pending_memos: BTreeMap<usize, Weak<Effect>>,
+    pending_effects: BTreeMap<usize, Weak<Effect>>,
 }
 
-// A thread-local stack to manage nested component renders.
 thread_local! {
-    static CONTEXT_STACK: RefCell<Vec<ComponentContext>> = RefCell::new(Vec::new());
-    static CURRENT_EFFECT: RefCell<Option<Rc<dyn Fn()>>> = RefCell::new(None);
+    // The observers whose reads are being tracked, innermost last. A `None`
+    // entry marks an untracked region, such as a component render.
+    static OBSERVERS: RefCell<Vec<Option<Rc<Effect>>>> = const { RefCell::new(Vec::new()) };
+    static NEXT_EFFECT_ID: Cell<usize> = const { Cell::new(0) };
+    static SCHEDULER: RefCell<Scheduler> = RefCell::new(Scheduler::default());
 }
 
 /// Provides a piece of state for a component.
+#[track_caller]
 pub fn use_state<T: Clone + 'static>(
     initial_value_fn: impl FnOnce() -> T,
 ) -> (impl Fn() -> T + Clone, impl Fn(T) + Clone) {
-    CONTEXT_STACK.with(|stack| {
-        let mut stack = stack.borrow_mut();
-        let current_context = stack
-            .last_mut()
-            .expect("`use_state` can only be called inside a component.");
-
-        if let Some(any_signal) = current_context.states.get(current_context.state_index) {
-            current_context.state_index += 1;
-            let signal = any_signal
-                .clone()
-                .downcast::<RefCell<Signal<T>>>()
-                .expect("Mismatched state type in `use_state` hook.");
-            return create_signal_from_rc(signal);
-        }
-
-        let initial_value = initial_value_fn();
-        let signal = Rc::new(RefCell::new(Signal {
-            value: initial_value,
-            subscribers: Vec::new(),
-        }));
-
-        current_context.states.push(signal.clone() as AnySignal);
-        current_context.state_index += 1;
-        create_signal_from_rc(signal)
-    })
+    let signal = use_hook_slot("use_state", || create_rw_signal(initial_value_fn()));
+    signal_fns(*signal)
 }
 
 /// A reactive signal that holds a value.
+///
+/// Returns a `(getter, setter)` pair of closures. Use [`create_rw_signal`] for
+/// a `Copy` handle that can be stored in structs or passed around by name.
 pub fn create_signal<T: Clone + 'static>(
     initial_value: T,
 ) -> (impl Fn() -> T + Clone, impl Fn(T) + Clone) {
-    let signal = Rc::new(RefCell::new(Signal {
-        value: initial_value,
-        subscribers: Vec::new(),
-    }));
-    create_signal_from_rc(signal)
+    signal_fns(create_rw_signal(initial_value))
 }
 
-// Helper to create getter/setter from a signal Rc.
-fn create_signal_from_rc<T: Clone + 'static>(
-    signal: Rc<RefCell<Signal<T>>>,
+// Helper to create getter/setter closures from a signal handle.
+fn signal_fns<T: Clone + 'static>(
+    signal: RwSignal<T>,
 ) -> (impl Fn() -> T + Clone, impl Fn(T) + Clone) {
-    let getter = {
-        let signal = Rc::clone(&signal);
-        move || {
-            CURRENT_EFFECT.with(|e| {
-                if let Some(effect) = e.borrow().clone() {
-                    let mut s = signal.borrow_mut();
-                    if !s.subscribers.iter().any(|s_rc| Rc::ptr_eq(s_rc, &effect)) {
-                        s.subscribers.push(effect);
-                    }
-                }
-            });
-            signal.borrow().value.clone()
-        }
-    };
-
-    let setter = {
-        let signal = Rc::clone(&signal);
-        move |new_value: T| {
-            let subscribers = {
-                let mut s = signal.borrow_mut();
-                s.value = new_value;
-                s.subscribers.clone()
-            };
-            for effect in subscribers {
-                effect();
+    (
+        move || signal.get(),
+        move |new_value: T| signal.set(new_value),
+    )
+}
+
+impl Subscribers {
+    // Subscribes the current observer, if any.
+    fn track(self: &Rc<Self>) {
+        if let Some(effect) = current_observer() {
+            let mut subscribers = self.0.borrow_mut();
+            let weak_effect = Rc::downgrade(&effect);
+            if !subscribers.iter().any(|s| s.ptr_eq(&weak_effect)) {
+                subscribers.push(weak_effect);
+                effect.sources.borrow_mut().push(Rc::clone(self));
             }
         }
-    };
+    }
 
-    (getter, setter)
+    // Queues every live subscriber after a change. The queued effects run
+    // when the enclosing batch ends.
+    fn notify(&self) {
+        let mut subscribers = self.0.borrow_mut();
+        subscribers.retain(|s| s.strong_count() > 0);
+        SCHEDULER.with(|s| {
+            let mut s = s.borrow_mut();
+            for effect in subscribers.iter().filter_map(Weak::upgrade) {
+                let queue = if effect.is_memo {
+                    &mut s.pending_memos
+                } else {
+                    &mut s.pending_effects
+                };
+                queue.insert(effect.id, Rc::downgrade(&effect));
+            }
+        });
+    }
 }
 
-/// Creates an effect that runs once and re-runs when its dependencies change.
-pub fn use_effect(effect_fn: impl Fn() + 'static) {
-    CONTEXT_STACK.with(|stack| {
-        let mut stack = stack.borrow_mut();
-        let current_context = stack
-            .last_mut()
-            .expect("`use_effect` can only be called inside a component.");
-
-        if current_context.effects.get(current_context.effect_index).is_some() {
-            current_context.effect_index += 1;
-            return;
+/// Runs `f` as a single batch of updates.
+///
+/// Effects invalidated by signal writes inside the batch are queued and run
+/// once, after the outermost batch ends, instead of after every write. Memos
+/// are brought up to date before any effect runs, so effects never observe a
+/// partially applied update.
+pub fn batch<R>(f: impl FnOnce() -> R) -> R {
+    SCHEDULER.with(|s| {
+        let mut s = s.borrow_mut();
+        if s.batch_depth == 0 {
+            s.batch_id += 1;
         }
+        s.batch_depth += 1;
+    });
+    let result = f();
+    let is_outermost = SCHEDULER.with(|s| {
+        let mut s = s.borrow_mut();
+        s.batch_depth -= 1;
+        s.batch_depth == 0
+    });
+    if is_outermost {
+        flush();
+    }
+    result
+}
 
-        create_effect(effect_fn);
-        current_context.effects.push(());
-        current_context.effect_index += 1;
+// Returns the number of the batch being run, if any.
+fn current_batch() -> Option<usize> {
+    SCHEDULER.with(|s| {
+        let s = s.borrow();
+        (s.batch_depth > 0).then_some(s.batch_id)
     })
 }
 
+// Runs queued effects until none are left. Writes made by the effects
+// themselves are queued and picked up by the same loop.
+fn flush() {
+    let already_flushing =
+        SCHEDULER.with(|s| std::mem::replace(&mut s.borrow_mut().flushing, true));
+    if already_flushing {
+        return;
+    }
+
+    loop {
+        let next = SCHEDULER.with(|s| {
+            let mut s = s.borrow_mut();
+            s.pending_memos
+                .pop_first()
+                .or_else(|| s.pending_effects.pop_first())
+                .map(|(_, effect)| effect)
+        });
+        match next {
+            // An effect that was dropped after being queued is skipped.
+            Some(effect) => {
+                if let Some(effect) = effect.upgrade() {
+                    run_effect(&effect);
+                }
+            }
+            None => break,
+        }
+    }
+
+    SCHEDULER.with(|s| s.borrow_mut().flushing = false);
+}
+
+/// Creates a derived signal whose value is cached until its dependencies change.
+///
+/// `compute_fn` is run immediately and re-run whenever a signal it reads is
+/// updated. Subscribers of the memo are only notified when the newly computed
+/// value is not equal to the cached one, so unchanged results never re-run
+/// downstream effects.
+pub fn create_memo<T: Clone + PartialEq + 'static>(
+    compute_fn: impl Fn() -> T + 'static,
+) -> impl Fn() -> T + Clone {
+    // The value is `None` only until the effect below has run for the first time.
+    let memo = Rc::new(RefCell::new(Signal {
+        value: None::<T>,
+        subscribers: Rc::default(),
+    }));
+
+    let memo_for_effect = Rc::clone(&memo);
+    spawn_effect(true, move || {
+        let new_value = compute_fn();
+        let subscribers = {
+            let mut m = memo_for_effect.borrow_mut();
+            if m.value.as_ref() == Some(&new_value) {
+                return;
+            }
+            m.value = Some(new_value);
+            Rc::clone(&m.subscribers)
+        };
+        subscribers.notify();
+    });
+
+    move || {
+        let m = memo.borrow();
+        m.subscribers.track();
+        m.value
+            .clone()
+            .expect("memo value is computed when the memo is created")
+    }
+}
+
+/// Creates an effect that runs once and re-runs when its dependencies change.
+///
+/// The effect is created on the first render only and lives as long as the
+/// component.
+#[track_caller]
+pub fn use_effect(effect_fn: impl Fn() + 'static) {
+    use_hook_slot("use_effect", || create_effect(effect_fn));
+}
+
 /// Creates an effect that re-runs when its dependencies change.
+///
+/// Dependencies are collected again on every run, so a signal that is only
+/// read conditionally stops triggering the effect once it is no longer read.
+/// The effect belongs to the current scope (see [`create_scope`]) and is
+/// dropped, along with its subscriptions, when that scope is disposed. An
+/// effect created while another effect is running is disposed when the outer
+/// effect re-runs.
 pub fn create_effect(effect_fn: impl Fn() + 'static) {
-    let effect = Rc::new(move || {
-        effect_fn();
-    });
+    spawn_effect(false, effect_fn);
+}
 
-    CURRENT_EFFECT.with(|e| e.borrow_mut().replace(effect.clone()));
-    effect();
-    CURRENT_EFFECT.with(|e| e.borrow_mut().take());
+/// Runs `f` without subscribing the current effect to the signals it reads.
+///
+/// Use it to peek at a signal's value inside an effect or memo when changes to
+/// that signal should not cause a re-run.
+pub fn untrack<R>(f: impl FnOnce() -> R) -> R {
+    with_observer(None, f)
 }
 
-/// A helper function to be called by the `#[component]` macro.
-pub fn with_component_context<F, R>(f: F) -> R
-where
-    F: FnOnce() -> R,
-{
-    CONTEXT_STACK.with(|s| s.borrow_mut().push(ComponentContext::default()));
+// Runs `f` with `observer` pushed onto the observer stack. The enclosing
+// observer resumes tracking once `f` returns.
+fn with_observer<R>(observer: Option<Rc<Effect>>, f: impl FnOnce() -> R) -> R {
+    OBSERVERS.with(|o| o.borrow_mut().push(observer));
     let result = f();
-    CONTEXT_STACK.with(|s| s.borrow_mut().pop());
+    OBSERVERS.with(|o| o.borrow_mut().pop());
     result
 }
 
-// A container for dependency-injected services.
-#[derive(Default)]
-pub struct ServiceContainer {
-    services: HashMap<TypeId, Rc<dyn Any>>,
+fn current_observer() -> Option<Rc<Effect>> {
+    OBSERVERS.with(|o| o.borrow().last().cloned().flatten())
 }
 
-impl ServiceContainer {
-    pub fn new() -> Self {
-        Self::default()
-    }
+/// Calls `callback` with the new and the previous value whenever the value
+/// returned by `source` changes.
+///
+/// Only the signals read by `source` are tracked; `callback` runs untracked,
+/// so reading other signals inside it never causes it to run again. Unlike an
+/// effect, the callback is not run for the initial value.
+pub fn watch<T: PartialEq + 'static>(
+    source: impl Fn() -> T + 'static,
+    callback: impl Fn(&T, &T) + 'static,
+) {
+    let last = RefCell::new(None::<T>);
+    create_effect(move || {
+        let new_value = source();
+        let previous = last.borrow_mut().take();
+        if let Some(previous) = previous.filter(|previous| *previous != new_value) {
+            untrack(|| callback(&new_value, &previous));
+        }
+        *last.borrow_mut() = Some(new_value);
+    });
 }
 
-thread_local! {
-    // A thread-local static container for services.
-    static SERVICE_CONTAINER: RefCell<ServiceContainer> = RefCell::new(ServiceContainer::new());
+// Creates an effect, attaches it to its owner and runs it for the first time.
+fn spawn_effect(is_memo: bool, run: impl Fn() + 'static) {
+    let id = NEXT_EFFECT_ID.with(|next| {
+        let id = next.get();
+        next.set(id + 1);
+        id
+    });
+    let parent = current_owner();
+    let effect = Rc::new(Effect {
+        id,
+        is_memo,
+        run: Box::new(run),
+        sources: RefCell::default(),
+        owner: Owner::child_of(&parent),
+    });
+    parent.add_effect(Rc::clone(&effect));
+
+    run_effect(&effect);
 }
 
-/// Provides a service to the application's global DI container.
-///
-/// The service is stored by its type, so only one instance of any
-/// given type can be provided.
-pub fn provide_service<T: 'static>(service: T) {
-    SERVICE_CONTAINER.with(|sc| {
-        sc.borrow_mut()
-            .services
-            .insert(TypeId::of::<T>(), Rc::new(service));
+// Runs an effect, re-collecting the signals it depends on. Writes made while
+// it runs are batched and flushed once it has finished.
+fn run_effect(effect: &Rc<Effect>) {
+    effect.reset();
+    batch(|| {
+        with_observer(Some(Rc::clone(effect)), || {
+            with_owner(Rc::clone(&effect.owner), || (effect.run)())
+        })
     });
 }
 
-/// Injects a service from the global DI container.
-///
-/// This hook retrieves a shared reference (`Rc`) to a service that was
-/// previously provided via `provide_service`.
-///
-/// # Panics
-///
-/// This function will panic if the requested service (`T`) has not been
-/// provided, or if the stored service cannot be downcast to the requested type.
-pub fn inject<T: 'static>() -> Rc<T> {
-    SERVICE_CONTAINER.with(|sc| {
-        let sc = sc.borrow();
-        let service = sc
-            .services
-            .get(&TypeId::of::<T>())
-            .expect("Service not provided: Make sure to call `provide_service` before `inject`.");
-
-        service
-            .clone()
-            .downcast::<T>()
-            .expect("Failed to downcast service to the requested type.")
-    })
+impl Effect {
+    // Leaves every subscriber list joined during the last run and tears down
+    // everything created by it.
+    fn reset(self: &Rc<Self>) {
+        self.unsubscribe();
+        self.owner.clean();
+    }
+
+    // Called by the owning scope when it is disposed.
+    fn dispose(self: &Rc<Self>) {
+        self.unsubscribe();
+        self.owner.dispose();
+    }
+
+    fn unsubscribe(self: &Rc<Self>) {
+        let this = Rc::downgrade(self);
+        for source in self.sources.take() {
+            source.0.borrow_mut().retain(|s| !s.ptr_eq(&this));
+        }
+    }
+}
+
+impl Drop for Effect {
+    // Removes the dropped effect from the subscriber lists it is still in, so
+    // signals that are never written again do not accumulate dead entries.
+    fn drop(&mut self) {
+        for source in self.sources.take() {
+            if let Ok(mut subscribers) = source.0.try_borrow_mut() {
+                subscribers.retain(|s| s.strong_count() > 0);
+            }
+        }
+    }
 }
 
 // --- Server Function Support ---
@@ -207,4 +391,33 @@ use std::sync::Arc;
 
 pub type ServerFn = Arc<
     dyn Fn(Value) -> Pin<Box<dyn Future<Output = Result<Value, String>> + Send>> + Send + Sync,
->;
\ No newline at end of file
+>;
+
+/// An error raised on the client when calling a `#[server]` function fails
+/// before the function itself returns, for example because the network
+/// request failed or the response could not be deserialized.
+///
+/// The error type of a server function must implement
+/// `From<ServerFnError>`, which `String` already does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerFnError(String);
+
+impl ServerFnError {
+    pub fn new(message: impl Into<String>) -> Self {
+        ServerFnError(message.into())
+    }
+}
+
+impl std::fmt::Display for ServerFnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ServerFnError {}
+
+impl From<ServerFnError> for String {
+    fn from(error: ServerFnError) -> String {
+        error.0
+    }
+}
\ No newline at end of file
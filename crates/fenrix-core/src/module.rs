@@ -0,0 +1,126 @@
+//! Constructor-based service wiring.
+//!
+//! A type implementing [`Injectable`], usually through the `#[injectable]`
+//! attribute, knows which services it needs and how to build itself from
+//! them. A [`Module`] registers a whole graph of such types at once, checking
+//! up front that every dependency is provided, so a missing service is
+//! reported when the application starts instead of at its first `inject`.
+
+use crate::di::{is_provided, DiError};
+use crate::provide_factory;
+use std::any::{type_name, TypeId};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+/// A service the container can construct by injecting its dependencies.
+///
+/// `#[injectable]` implements it for a struct whose fields are `Rc<Dep>`:
+/// every such field is a dependency and is filled by `inject::<Dep>()`.
+pub trait Injectable: 'static {
+    /// The services `construct` injects.
+    fn dependencies() -> Vec<Dependency>;
+
+    /// Builds the service, injecting its dependencies.
+    fn construct() -> Self;
+}
+
+/// A service that an [`Injectable`] depends on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dependency {
+    type_id: TypeId,
+    type_name: &'static str,
+}
+
+impl Dependency {
+    /// The dependency on the service registered under `T`.
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        Dependency {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+        }
+    }
+
+    /// The name of the service's type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+// A service the module registers.
+struct Registration {
+    type_id: TypeId,
+    type_name: &'static str,
+    dependencies: Vec<Dependency>,
+    provide: Box<dyn FnOnce()>,
+}
+
+/// A list of services that are provided to the global container together.
+///
+/// Services are registered as singleton factories, so they are only built
+/// when first injected, and the order they are added in does not matter.
+///
+/// ```ignore
+/// Module::new()
+///     .service(Config::from_env())
+///     .injectable::<ApiClient>()
+///     .injectable::<AuthService>()
+///     .provide()
+///     .expect("the service graph is complete");
+/// ```
+#[derive(Default)]
+pub struct Module {
+    registrations: Vec<Registration>,
+}
+
+impl Module {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service that is built by [`Injectable::construct`].
+    pub fn injectable<T: Injectable>(mut self) -> Self {
+        self.registrations.push(Registration {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            dependencies: T::dependencies(),
+            provide: Box::new(|| provide_factory(|| Rc::new(T::construct()))),
+        });
+        self
+    }
+
+    /// Adds a ready-made service, like `provide_service` does.
+    pub fn service<T: 'static>(mut self, service: T) -> Self {
+        self.registrations.push(Registration {
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            dependencies: Vec::new(),
+            provide: Box::new(|| crate::provide_service(service)),
+        });
+        self
+    }
+
+    /// Checks that every dependency of every service is provided, by this
+    /// module or already by the container, and then provides the services.
+    /// Services provided with `provide_context` do not count: the services
+    /// are singletons, built outside of any scope.
+    ///
+    /// Nothing is provided if a dependency is missing.
+    pub fn provide(self) -> Result<(), DiError> {
+        let registered: HashSet<TypeId> = self.registrations.iter().map(|r| r.type_id).collect();
+        for registration in &self.registrations {
+            let missing = registration.dependencies.iter().find(|dependency| {
+                !registered.contains(&dependency.type_id) && !is_provided(dependency.type_id)
+            });
+            if let Some(dependency) = missing {
+                return Err(DiError::MissingDependency {
+                    service: registration.type_name,
+                    dependency: dependency.type_name,
+                });
+            }
+        }
+        for registration in self.registrations {
+            (registration.provide)();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+//! Reactive ownership scopes.
+//!
+//! Every effect, signal, cleanup callback and child scope is owned by the
+//! scope that was current when it was created. Disposing a scope tears all of
+//! them down together, which is how a component removed from the page stops
+//! its effects, event listeners and timers.
+
+use crate::signal::SignalId;
+use crate::Effect;
+use std::any::{Any, TypeId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+// A node in the ownership tree.
+#[derive(Default)]
+pub(crate) struct Owner {
+    parent: Weak<Owner>,
+    effects: RefCell<Vec<Rc<Effect>>>,
+    children: RefCell<Vec<Rc<Owner>>>,
+    cleanups: RefCell<Vec<Box<dyn FnOnce()>>>,
+    signals: RefCell<Vec<SignalId>>,
+    // Values visible to everything created in this owner and its descendants.
+    contexts: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
+    disposed: Cell<bool>,
+}
+
+thread_local! {
+    static CURRENT_OWNER: RefCell<Option<Rc<Owner>>> = const { RefCell::new(None) };
+    // Owns everything created outside of any scope. It is never disposed.
+    static ROOT_OWNER: Rc<Owner> = Rc::default();
+}
+
+impl Owner {
+    // Creates an owner that is disposed together with `parent`. The caller is
+    // responsible for attaching it to the parent.
+    pub(crate) fn child_of(parent: &Rc<Owner>) -> Rc<Owner> {
+        Rc::new(Owner {
+            parent: Rc::downgrade(parent),
+            ..Owner::default()
+        })
+    }
+
+    pub(crate) fn add_effect(&self, effect: Rc<Effect>) {
+        self.effects.borrow_mut().push(effect);
+    }
+
+    pub(crate) fn add_signal(&self, signal: SignalId) {
+        self.signals.borrow_mut().push(signal);
+    }
+
+    pub(crate) fn provide_context<T: 'static>(&self, value: T) {
+        self.contexts
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Rc::new(value));
+    }
+
+    // Looks a context up in this owner and then in each of its ancestors.
+    pub(crate) fn use_context<T: Clone + 'static>(&self) -> Option<T> {
+        if let Some(value) = self.local_context() {
+            return Some(value);
+        }
+        self.parent()?.use_context()
+    }
+
+    // Looks a context up in this owner only.
+    pub(crate) fn local_context<T: Clone + 'static>(&self) -> Option<T> {
+        let contexts = self.contexts.borrow();
+        contexts
+            .get(&TypeId::of::<T>())?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    pub(crate) fn parent(&self) -> Option<Rc<Owner>> {
+        self.parent.upgrade()
+    }
+
+    // Tears down everything created in this owner so far, leaving it usable.
+    pub(crate) fn clean(&self) {
+        for child in self.children.take() {
+            child.dispose();
+        }
+        for effect in self.effects.take() {
+            effect.dispose();
+        }
+        // Cleanups run in reverse order of registration, like destructors.
+        let cleanups = self.cleanups.take();
+        for cleanup in cleanups.into_iter().rev() {
+            cleanup();
+        }
+        // Signals and contexts go last, since cleanups may still use them.
+        for signal in self.signals.take() {
+            signal.dispose();
+        }
+        self.contexts.take();
+    }
+
+    pub(crate) fn dispose(&self) {
+        self.disposed.set(true);
+        self.clean();
+    }
+}
+
+// Returns the owner that new reactive nodes should be attached to.
+pub(crate) fn current_owner() -> Rc<Owner> {
+    CURRENT_OWNER
+        .with(|o| o.borrow().clone())
+        .unwrap_or_else(|| ROOT_OWNER.with(Rc::clone))
+}
+
+// Runs `f` with `owner` as the current owner.
+pub(crate) fn with_owner<R>(owner: Rc<Owner>, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_OWNER.with(|o| o.borrow_mut().replace(owner));
+    let result = f();
+    CURRENT_OWNER.with(|o| *o.borrow_mut() = previous);
+    result
+}
+
+/// A handle to a reactive scope created by [`create_root`] or [`create_scope`].
+///
+/// Handles are cheap to clone; all clones refer to the same scope.
+#[derive(Clone)]
+pub struct Scope {
+    owner: Rc<Owner>,
+}
+
+impl Scope {
+    /// Disposes the scope: every effect and child scope created in it is torn
+    /// down and its `on_cleanup` callbacks are run. Disposing twice is a no-op.
+    pub fn dispose(&self) {
+        if self.owner.disposed.get() {
+            return;
+        }
+        if let Some(parent) = self.owner.parent.upgrade() {
+            parent
+                .children
+                .borrow_mut()
+                .retain(|child| !Rc::ptr_eq(child, &self.owner));
+        }
+        self.owner.dispose();
+    }
+
+    /// Returns `true` once the scope has been disposed, either directly or
+    /// because its parent was.
+    pub fn is_disposed(&self) -> bool {
+        self.owner.disposed.get()
+    }
+
+    /// Runs `f` inside this scope, so anything it creates is owned by it.
+    ///
+    /// This is useful for callbacks (like timers or async tasks) that create
+    /// reactive nodes after the scope's initial setup has returned.
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        with_owner(Rc::clone(&self.owner), f)
+    }
+}
+
+/// Runs `f` in a new root scope that is not owned by the current scope.
+///
+/// The root lives until [`Scope::dispose`] is called on the returned handle.
+pub fn create_root<R>(f: impl FnOnce() -> R) -> (R, Scope) {
+    let owner = Rc::new(Owner::default());
+    let result = with_owner(Rc::clone(&owner), f);
+    (result, Scope { owner })
+}
+
+/// Runs `f` in a new scope owned by the current scope.
+///
+/// The scope is disposed when its parent is disposed (for example, when the
+/// effect it was created in re-runs), or earlier through the returned handle.
+pub fn create_scope<R>(f: impl FnOnce() -> R) -> (R, Scope) {
+    let parent = current_owner();
+    let owner = Owner::child_of(&parent);
+    parent.children.borrow_mut().push(Rc::clone(&owner));
+    let result = with_owner(Rc::clone(&owner), f);
+    (result, Scope { owner })
+}
+
+/// Registers a callback to run when the current scope is disposed.
+///
+/// Inside an effect, the callback runs before the effect's next run. Use it to
+/// remove event listeners, clear timers or release any other resource tied to
+/// the lifetime of a component.
+pub fn on_cleanup(f: impl FnOnce() + 'static) {
+    current_owner().cleanups.borrow_mut().push(Box::new(f));
+}
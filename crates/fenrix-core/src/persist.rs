@@ -0,0 +1,165 @@
+//! Signals whose value is kept in a key-value storage.
+//!
+//! The storage is pluggable: in the browser it is `localStorage` or
+//! `sessionStorage` (see `fenrix_dom::WebStorage`), and [`MemoryStorage`]
+//! keeps everything in memory so persisted state can be tested natively.
+
+use crate::signal::RwSignal;
+use crate::{create_effect, create_rw_signal, inject, on_cleanup};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Called with the key and the new value (`None` once it is removed) when a
+/// storage is changed from outside the application.
+pub type StorageListener = Rc<dyn Fn(&str, Option<&str>)>;
+
+/// A string key-value store that persisted signals are saved in.
+pub trait Storage {
+    /// Returns the value stored under `key`.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores `value` under `key`.
+    fn set(&self, key: &str, value: &str);
+
+    /// Removes the value stored under `key`.
+    fn remove(&self, key: &str);
+
+    /// Calls `listener` whenever the storage is changed from outside the
+    /// application, such as by another tab. Changes made through `set` and
+    /// `remove` are not reported. Returns a function that unsubscribes.
+    fn subscribe(&self, listener: StorageListener) -> Box<dyn FnOnce()>;
+}
+
+/// A [`Storage`] that keeps its values in memory.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: RefCell<HashMap<String, String>>,
+    listeners: Rc<RefCell<Vec<(usize, StorageListener)>>>,
+    next_id: Cell<usize>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Changes `key` the way another tab would: the value is stored (or
+    /// removed, for `None`) and subscribers are notified.
+    pub fn set_from_other_tab(&self, key: &str, value: Option<&str>) {
+        match value {
+            Some(value) => self.set(key, value),
+            None => self.remove(key),
+        }
+        let listeners: Vec<_> = self
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| Rc::clone(listener))
+            .collect();
+        for listener in listeners {
+            listener(key, value);
+        }
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.values
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    fn remove(&self, key: &str) {
+        self.values.borrow_mut().remove(key);
+    }
+
+    fn subscribe(&self, listener: StorageListener) -> Box<dyn FnOnce()> {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        self.listeners.borrow_mut().push((id, listener));
+        let listeners = Rc::clone(&self.listeners);
+        Box::new(move || listeners.borrow_mut().retain(|(other, _)| *other != id))
+    }
+}
+
+/// Creates a signal that is saved in the `dyn Storage` service under `key`.
+///
+/// Provide the storage first, for example with
+/// `provide_service_as::<dyn Storage>(Rc::new(WebStorage::local()))`. See
+/// [`create_persisted_signal_in`] for how the value is kept in sync.
+///
+/// # Panics
+///
+/// Panics if no `dyn Storage` service is provided.
+pub fn create_persisted_signal<T>(key: impl Into<String>, default: T) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    create_persisted_signal_in(inject::<dyn Storage>(), key, default)
+}
+
+/// Creates a signal that is saved in `storage` under `key`.
+///
+/// The signal starts out with the stored value, or `default` if there is
+/// none or it cannot be deserialized. Every change is serialized to JSON and
+/// written back. When the value is changed from outside the application, as
+/// by another tab, the signal is updated without writing the value back; it
+/// falls back to `default` when the value is removed, and the key stays
+/// removed. It stops listening when the current scope is disposed.
+pub fn create_persisted_signal_in<T>(
+    storage: Rc<dyn Storage>,
+    key: impl Into<String>,
+    default: T,
+) -> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    let key: Rc<str> = key.into().into();
+    let load = |json: &str| serde_json::from_str::<T>(json).ok();
+    let initial = storage.get(&key).and_then(|json| load(&json));
+    let signal = create_rw_signal(initial.unwrap_or_else(|| default.clone()));
+
+    // The JSON of the value last read from or written to the storage. Only
+    // values that differ from it are written, so values that came from the
+    // storage, initially or after an outside change, are not written back,
+    // whenever the effect runs. Values that fail to serialize are not saved.
+    let synced = Rc::new(RefCell::new(signal.with(serde_json::to_string).ok()));
+    create_effect({
+        let storage = Rc::clone(&storage);
+        let key = Rc::clone(&key);
+        let synced = Rc::clone(&synced);
+        move || {
+            let Ok(json) = signal.with(serde_json::to_string) else {
+                return;
+            };
+            if synced.borrow().as_deref() != Some(json.as_str()) {
+                storage.set(&key, &json);
+                *synced.borrow_mut() = Some(json);
+            }
+        }
+    });
+
+    let unsubscribe = storage.subscribe(Rc::new(move |changed, value| {
+        if changed != &*key {
+            return;
+        }
+        let value = match value {
+            Some(json) => load(json),
+            None => Some(default.clone()),
+        };
+        if let Some(value) = value {
+            *synced.borrow_mut() = serde_json::to_string(&value).ok();
+            signal.set(value);
+        }
+    }));
+    on_cleanup(unsubscribe);
+
+    signal
+}
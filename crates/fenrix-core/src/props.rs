@@ -0,0 +1,79 @@
+//! Component props.
+//!
+//! `#[component]` turns the parameters of a component into a props struct
+//! with a builder, and `rsx!` builds the props of `<Card title={...}>` with
+//! [`component_props`] before calling the component through [`Component`].
+//! Components without parameters stay plain functions, so they can still be
+//! used wherever a `fn() -> Node` is expected, such as in routes.
+
+/// The props of a component, built with a builder that `#[component]`
+/// generates.
+pub trait Props {
+    type Builder;
+
+    fn builder() -> Self::Builder;
+}
+
+/// The builder of a component without props.
+pub struct NoProps;
+
+impl NoProps {
+    pub fn build(self) {}
+}
+
+impl Props for () {
+    type Builder = NoProps;
+
+    fn builder() -> NoProps {
+        NoProps
+    }
+}
+
+/// Marks the [`Component`] implementation of functions without parameters.
+#[doc(hidden)]
+pub struct WithoutProps;
+
+/// Marks the [`Component`] implementation of functions taking props.
+#[doc(hidden)]
+pub struct WithProps;
+
+/// A component function, called with props `P`.
+///
+/// `Marker` tells the two implementations apart; it is always inferred.
+pub trait Component<P, Marker> {
+    type Output;
+
+    fn call(self, props: P) -> Self::Output;
+}
+
+impl<F, R> Component<(), WithoutProps> for F
+where
+    F: FnOnce() -> R,
+{
+    type Output = R;
+
+    fn call(self, (): ()) -> R {
+        self()
+    }
+}
+
+impl<F, P, R> Component<P, WithProps> for F
+where
+    F: FnOnce(P) -> R,
+    P: Props,
+{
+    type Output = R;
+
+    fn call(self, props: P) -> R {
+        self(props)
+    }
+}
+
+/// Returns the props builder of `component`.
+pub fn component_props<F, P, Marker>(_component: &F) -> P::Builder
+where
+    F: Component<P, Marker>,
+    P: Props,
+{
+    P::builder()
+}
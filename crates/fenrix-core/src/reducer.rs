@@ -0,0 +1,80 @@
+//! Reducer state: state changed only by dispatching actions, optionally
+//! through middleware.
+
+use crate::hooks::use_hook_slot;
+use crate::signal::ReadSignal;
+use crate::{create_rw_signal, untrack};
+use std::rc::Rc;
+
+/// A reducer middleware, wrapped around every dispatched action.
+///
+/// It receives the current state, the action and `next`, which passes the
+/// action on to the remaining middleware and then to the reducer. It returns
+/// the new state, so it can log the action, change it, or skip `next`
+/// altogether and compute the state itself.
+pub type Middleware<S, A> = Box<dyn Fn(&S, A, &dyn Fn(A) -> S) -> S>;
+
+/// Provides state for a component that is only changed by dispatching actions.
+///
+/// Returns the state signal and a `dispatch` function. Dispatching an action
+/// replaces the state with `reducer(&state, action)`. Like `use_state`, the
+/// state persists across re-renders of the component.
+#[track_caller]
+pub fn use_reducer<S: Clone + 'static, A: 'static>(
+    initial_state: S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+) -> (ReadSignal<S>, impl Fn(A) + Clone) {
+    use_reducer_with_middleware(initial_state, reducer, Vec::new())
+}
+
+/// Like [`use_reducer`], but every action first goes through `middleware`,
+/// in order.
+///
+/// ```ignore
+/// let logger: Middleware<Form, FormAction> = Box::new(|state, action, next| {
+///     log(&format!("{:?}", action));
+///     next(action)
+/// });
+/// let (form, dispatch) = use_reducer_with_middleware(Form::default(), reduce, vec![logger]);
+/// ```
+#[track_caller]
+pub fn use_reducer_with_middleware<S: Clone + 'static, A: 'static>(
+    initial_state: S,
+    reducer: impl Fn(&S, A) -> S + 'static,
+    middleware: Vec<Middleware<S, A>>,
+) -> (ReadSignal<S>, impl Fn(A) + Clone) {
+    let slot = use_hook_slot("use_reducer", || {
+        let state = create_rw_signal(initial_state);
+        let reducer = Rc::new(Reducer {
+            reducer,
+            middleware,
+        });
+        let dispatch: Rc<dyn Fn(A)> = Rc::new(move |action| {
+            // The state is cloned, so middleware may dispatch again.
+            let current = untrack(|| state.get());
+            let next = reducer.run(0, &current, action);
+            state.set(next);
+        });
+        (state, dispatch)
+    });
+
+    let (state, dispatch) = (slot.0, Rc::clone(&slot.1));
+    (state.read_only(), move |action| dispatch(action))
+}
+
+struct Reducer<S, A, F> {
+    reducer: F,
+    middleware: Vec<Middleware<S, A>>,
+}
+
+impl<S, A, F: Fn(&S, A) -> S> Reducer<S, A, F> {
+    // Passes `action` through the middleware from `index` on, then reduces it.
+    fn run(&self, index: usize, state: &S, action: A) -> S {
+        match self.middleware.get(index) {
+            Some(middleware) => {
+                middleware(state, action, &|action| self.run(index + 1, state, action))
+            }
+            None => (self.reducer)(state, action),
+        }
+    }
+}
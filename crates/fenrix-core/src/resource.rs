@@ -0,0 +1,175 @@
+//! Async data tied to the reactive graph.
+
+use crate::boundary::{register_resource, throw_error};
+use crate::executor::spawn_local;
+use crate::signal::RwSignal;
+use crate::{batch, create_effect, create_rw_signal, on_cleanup, untrack};
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+
+/// The state of an async request started by [`create_resource`].
+///
+/// Like signal handles, a resource is `Copy` and belongs to the scope it was
+/// created in. Every accessor is reactive, so reading one inside an effect
+/// re-runs the effect when the request starts or finishes. Reading a resource
+/// inside a `<Suspense>` boundary also makes the boundary wait for it.
+pub struct Resource<T, E> {
+    data: RwSignal<Option<T>>,
+    error: RwSignal<Option<E>>,
+    loading: RwSignal<bool>,
+    // Written to force a new request for the same input.
+    trigger: RwSignal<()>,
+}
+
+/// Loads data asynchronously whenever the value returned by `source` changes.
+///
+/// `fetcher` is called with the current value of `source` and the future it
+/// returns is polled on the current thread. When it resolves, [`data`] or
+/// [`error`] is updated. If `source` changes while a request is still pending,
+/// that request is cancelled by dropping its future and a new one is started,
+/// so a slow, stale response can never overwrite a newer one. The same happens
+/// when the owning scope is disposed.
+///
+/// The client half of a `#[server]` function can be used as the fetcher
+/// directly:
+///
+/// ```ignore
+/// let (user_id, set_user_id) = create_signal(1);
+/// let user = create_resource(user_id, get_user_from_db);
+/// ```
+///
+/// [`data`]: Resource::data
+/// [`error`]: Resource::error
+pub fn create_resource<S, T, E, Fut>(
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fut + 'static,
+) -> Resource<T, E>
+where
+    T: 'static,
+    E: 'static,
+    Fut: Future<Output = Result<T, E>> + 'static,
+{
+    let resource = Resource {
+        data: create_rw_signal(None),
+        error: create_rw_signal(None),
+        loading: create_rw_signal(false),
+        trigger: create_rw_signal(()),
+    };
+
+    create_effect(move || {
+        resource.trigger.with(|_| ());
+        let input = source();
+        let request = untrack(|| fetcher(input));
+        resource.loading.set(true);
+
+        let task = spawn_local(async move {
+            let result = request.await;
+            batch(|| {
+                match result {
+                    Ok(data) => {
+                        resource.data.set(Some(data));
+                        resource.error.set(None);
+                    }
+                    Err(error) => {
+                        resource.data.set(None);
+                        resource.error.set(Some(error));
+                    }
+                }
+                resource.loading.set(false);
+            });
+        });
+        // Runs when the source changes, on `refetch` and when the scope is
+        // disposed. A request that already finished is not affected.
+        on_cleanup(move || task.abort());
+    });
+
+    resource
+}
+
+impl<T: 'static, E: 'static> Resource<T, E> {
+    /// Returns `true` while a request is in flight.
+    ///
+    /// The previous [`data`](Self::data) or [`error`](Self::error) stays
+    /// available while a new request is loading.
+    pub fn loading(&self) -> bool {
+        self.track();
+        self.loading.get()
+    }
+
+    /// Returns the data from the last successful request, or `None` if no
+    /// request has succeeded yet or the last one failed.
+    pub fn data(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.track();
+        self.data.get()
+    }
+
+    /// Calls `f` with a reference to the current data, without cloning it.
+    pub fn with_data<R>(&self, f: impl FnOnce(Option<&T>) -> R) -> R {
+        self.track();
+        self.data.with(|data| f(data.as_ref()))
+    }
+
+    /// Returns the error from the last request, if it failed.
+    pub fn error(&self) -> Option<E>
+    where
+        E: Clone,
+    {
+        self.track();
+        self.error.get()
+    }
+
+    /// Returns the current data like [`data`](Self::data), but surfaces a
+    /// failed request to the nearest `<ErrorBoundary>` instead of returning it.
+    ///
+    /// The error of a previous request is not surfaced while a new one is
+    /// loading, so an error boundary can be reset together with a `refetch`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request failed and no error boundary covers the caller.
+    pub fn read(&self) -> Option<T>
+    where
+        T: Clone,
+        E: Clone + Into<Box<dyn Error>>,
+    {
+        if !self.loading() {
+            if let Some(error) = self.error() {
+                throw_error(error);
+            }
+        }
+        self.data()
+    }
+
+    /// Starts a new request with the current source value, cancelling any
+    /// request that is still pending.
+    pub fn refetch(&self) {
+        self.trigger.set(());
+    }
+
+    fn track(&self) {
+        register_resource(self.loading.read_only());
+    }
+}
+
+// Implemented by hand so that resources are `Copy` even when `T` and `E` are not.
+impl<T, E> Clone for Resource<T, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E> Copy for Resource<T, E> {}
+
+impl<T, E> fmt::Debug for Resource<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("data", &self.data)
+            .field("error", &self.error)
+            .field("loading", &self.loading)
+            .finish()
+    }
+}
@@ -0,0 +1,285 @@
+//! Copyable signal handles backed by a thread-local arena.
+//!
+//! The value of every signal lives in the arena; a handle is just an index
+//! into it. That makes [`ReadSignal`], [`WriteSignal`] and [`RwSignal`] `Copy`,
+//! so they can be stored in structs, named in signatures and passed as props
+//! without boxing. A signal is removed from the arena when the scope it was
+//! created in is disposed.
+
+use crate::owner::current_owner;
+use crate::{batch, Signal};
+use std::any::Any;
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SignalId {
+    index: usize,
+    // Bumped every time a slot is reused, so stale handles can be detected.
+    generation: u32,
+}
+
+#[derive(Default)]
+struct SignalArena {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+#[derive(Default)]
+struct Slot {
+    generation: u32,
+    // Holds an `Rc<RefCell<Signal<T>>>` while the signal is alive.
+    signal: Option<Rc<dyn Any>>,
+}
+
+thread_local! {
+    static ARENA: RefCell<SignalArena> = RefCell::new(SignalArena::default());
+}
+
+impl SignalId {
+    // Stores a new signal and makes the current scope responsible for it.
+    fn new<T: 'static>(value: T) -> SignalId {
+        let signal: Rc<dyn Any> = Rc::new(RefCell::new(Signal {
+            value,
+            subscribers: Rc::default(),
+        }));
+        let id = ARENA.with(|arena| {
+            let mut arena = arena.borrow_mut();
+            let index = arena.free.pop().unwrap_or_else(|| {
+                arena.slots.push(Slot::default());
+                arena.slots.len() - 1
+            });
+            let slot = &mut arena.slots[index];
+            slot.signal = Some(signal);
+            SignalId {
+                index,
+                generation: slot.generation,
+            }
+        });
+        current_owner().add_signal(id);
+        id
+    }
+
+    // Looks the signal up, returning `None` once it has been disposed.
+    fn try_get<T: 'static>(self) -> Option<Rc<RefCell<Signal<T>>>> {
+        let signal = ARENA.with(|arena| {
+            let arena = arena.borrow();
+            let slot = arena.slots.get(self.index)?;
+            if slot.generation != self.generation {
+                return None;
+            }
+            slot.signal.clone()
+        })?;
+        Some(
+            signal
+                .downcast()
+                .unwrap_or_else(|_| panic!("signal slot holds a value of another type")),
+        )
+    }
+
+    fn get<T: 'static>(self) -> Rc<RefCell<Signal<T>>> {
+        self.try_get()
+            .expect("Tried to read a signal after the scope that created it was disposed.")
+    }
+
+    pub(crate) fn dispose(self) {
+        ARENA.with(|arena| {
+            let mut arena = arena.borrow_mut();
+            let slot = &mut arena.slots[self.index];
+            if slot.generation == self.generation {
+                // Dropped outside of the arena borrow, in case the value's
+                // destructor touches other signals.
+                let signal = slot.signal.take();
+                slot.generation = slot.generation.wrapping_add(1);
+                arena.free.push(self.index);
+                drop(arena);
+                drop(signal);
+            }
+        });
+    }
+
+    fn with<T: 'static, R>(self, f: impl FnOnce(&T) -> R) -> R {
+        let signal = self.get::<T>();
+        let signal = signal.borrow();
+        signal.subscribers.track();
+        f(&signal.value)
+    }
+
+    // Like `with`, but returns `None` instead of panicking once disposed.
+    fn try_with<T: 'static, R>(self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        let signal = self.try_get::<T>()?;
+        let signal = signal.borrow();
+        signal.subscribers.track();
+        Some(f(&signal.value))
+    }
+
+    fn update<T: 'static>(self, f: impl FnOnce(&mut T)) {
+        // Writing to a disposed signal is a no-op, so late callbacks (such as
+        // a request finishing after its component was removed) are harmless.
+        let Some(signal) = self.try_get::<T>() else {
+            return;
+        };
+        batch(|| {
+            let subscribers = {
+                let mut signal = signal.borrow_mut();
+                f(&mut signal.value);
+                Rc::clone(&signal.subscribers)
+            };
+            subscribers.notify();
+        });
+    }
+}
+
+/// Creates a signal and returns a single handle that can both read and write it.
+///
+/// The signal belongs to the current scope and is disposed with it.
+pub fn create_rw_signal<T: 'static>(value: T) -> RwSignal<T> {
+    RwSignal {
+        id: SignalId::new(value),
+        ty: PhantomData,
+    }
+}
+
+/// The read half of a signal.
+pub struct ReadSignal<T> {
+    id: SignalId,
+    ty: PhantomData<fn() -> T>,
+}
+
+/// The write half of a signal.
+pub struct WriteSignal<T> {
+    id: SignalId,
+    ty: PhantomData<fn(T)>,
+}
+
+/// A signal handle that can both read and write.
+pub struct RwSignal<T> {
+    id: SignalId,
+    ty: PhantomData<fn(T) -> T>,
+}
+
+impl<T: 'static> ReadSignal<T> {
+    /// Returns a clone of the current value, subscribing the current effect.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scope that created the signal has been disposed.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.with(T::clone)
+    }
+
+    /// Calls `f` with a reference to the current value, subscribing the
+    /// current effect. Unlike [`get`](Self::get), this does not clone the value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scope that created the signal has been disposed.
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.id.with(f)
+    }
+
+    // Used where a signal may outlive its scope, such as a resource that is
+    // removed while a suspense boundary still refers to it.
+    pub(crate) fn try_with<R>(&self, f: impl FnOnce(&T) -> R) -> Option<R> {
+        self.id.try_with(f)
+    }
+}
+
+impl<T: 'static> WriteSignal<T> {
+    /// Replaces the value and notifies subscribers.
+    ///
+    /// Writing to a signal whose scope has been disposed does nothing.
+    pub fn set(&self, value: T) {
+        self.update(|v| *v = value);
+    }
+
+    /// Mutates the value in place and notifies subscribers.
+    ///
+    /// Writing to a signal whose scope has been disposed does nothing.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        self.id.update(f);
+    }
+}
+
+impl<T: 'static> RwSignal<T> {
+    /// See [`ReadSignal::get`].
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.read_only().get()
+    }
+
+    /// See [`ReadSignal::with`].
+    pub fn with<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        self.read_only().with(f)
+    }
+
+    /// See [`WriteSignal::set`].
+    pub fn set(&self, value: T) {
+        self.write_only().set(value);
+    }
+
+    /// See [`WriteSignal::update`].
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        self.write_only().update(f);
+    }
+
+    /// Returns a handle that can only read the signal.
+    pub fn read_only(&self) -> ReadSignal<T> {
+        ReadSignal {
+            id: self.id,
+            ty: PhantomData,
+        }
+    }
+
+    /// Returns a handle that can only write the signal.
+    pub fn write_only(&self) -> WriteSignal<T> {
+        WriteSignal {
+            id: self.id,
+            ty: PhantomData,
+        }
+    }
+
+    /// Splits the handle into its read and write halves.
+    pub fn split(&self) -> (ReadSignal<T>, WriteSignal<T>) {
+        (self.read_only(), self.write_only())
+    }
+}
+
+// Implemented by hand so that handles are `Copy` even when `T` is not.
+macro_rules! impl_handle_traits {
+    ($($handle:ident),*) => {$(
+        impl<T> Clone for $handle<T> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T> Copy for $handle<T> {}
+
+        // Two handles are equal when they refer to the same signal.
+        impl<T> PartialEq for $handle<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+
+        impl<T> Eq for $handle<T> {}
+
+        impl<T> fmt::Debug for $handle<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($handle))
+                    .field("id", &self.id)
+                    .finish()
+            }
+        }
+    )*};
+}
+
+impl_handle_traits!(ReadSignal, WriteSignal, RwSignal);
@@ -0,0 +1,49 @@
+//! Structs whose fields are tracked one by one.
+
+/// A struct that can be turned into a store of per-field signals.
+///
+/// Implement it with `#[derive(Store)]` from `fenrix_macros`, which generates
+/// a `Copy` store type named after the struct (`User` gets `UserStore`) with
+/// one accessor per field. Each accessor returns the field's [`RwSignal`], or,
+/// for fields marked `#[store]`, the nested store. Reading a field through the
+/// store only subscribes to that field, and writing it only notifies the
+/// effects that read it:
+///
+/// ```ignore
+/// #[derive(Clone, Store)]
+/// struct User {
+///     name: String,
+///     #[store]
+///     address: Address,
+/// }
+///
+/// #[derive(Clone, Store)]
+/// struct Address {
+///     city: String,
+/// }
+///
+/// let user = create_store(User { /* ... */ });
+/// create_effect(move || log(user.address().city().get()));
+/// // Does not re-run the effect above.
+/// user.name().set("GV".to_string());
+/// ```
+///
+/// The generated store also has `get`, which returns a snapshot of the whole
+/// struct (and therefore needs every field to be `Clone`), and `set`, which
+/// replaces every field at once. `set` compares each field with its current
+/// value and only notifies the readers of the fields that changed, so it
+/// needs every field to be `PartialEq`.
+///
+/// [`RwSignal`]: crate::RwSignal
+pub trait Store: Sized {
+    /// The generated store type.
+    type Store: Copy + 'static;
+
+    /// Moves every field into its own signal, owned by the current scope.
+    fn into_store(self) -> Self::Store;
+}
+
+/// Creates a store from `value`. See [`Store`].
+pub fn create_store<T: Store>(value: T) -> T::Store {
+    value.into_store()
+}
@@ -0,0 +1,105 @@
+use fenrix_core::{batch, create_effect, create_memo, create_signal};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[test]
+fn batch_runs_effect_once_for_multiple_writes() {
+    let (a, set_a) = create_signal(1);
+    let (b, set_b) = create_signal(2);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        create_effect(move || {
+            a();
+            b();
+            runs.set(runs.get() + 1);
+        });
+    }
+    assert_eq!(runs.get(), 1);
+
+    batch(|| {
+        set_a(10);
+        set_b(20);
+        set_a(30);
+    });
+    assert_eq!(
+        runs.get(),
+        2,
+        "Effect should run once after the batch ends."
+    );
+}
+
+#[test]
+fn effects_do_not_observe_half_updated_state() {
+    let (first, set_first) = create_signal("Ada".to_string());
+    let (last, set_last) = create_signal("Lovelace".to_string());
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    {
+        let seen = Rc::clone(&seen);
+        create_effect(move || seen.borrow_mut().push(format!("{} {}", first(), last())));
+    }
+
+    batch(|| {
+        set_first("Grace".to_string());
+        set_last("Hopper".to_string());
+    });
+    assert_eq!(*seen.borrow(), vec!["Ada Lovelace", "Grace Hopper"]);
+}
+
+#[test]
+fn nested_batches_flush_when_the_outermost_ends() {
+    let (count, set_count) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        let count = count.clone();
+        create_effect(move || {
+            count();
+            runs.set(runs.get() + 1);
+        });
+    }
+
+    batch(|| {
+        batch(|| set_count(1));
+        assert_eq!(runs.get(), 1, "Inner batch should not flush.");
+        set_count(2);
+    });
+    assert_eq!(runs.get(), 2);
+    assert_eq!(count(), 2);
+}
+
+#[test]
+fn memos_update_before_dependent_effects() {
+    let (count, set_count) = create_signal(1);
+    let doubled = {
+        let count = count.clone();
+        create_memo(move || count() * 2)
+    };
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    {
+        let seen = Rc::clone(&seen);
+        create_effect(move || seen.borrow_mut().push((count(), doubled())));
+    }
+
+    set_count(2);
+    assert_eq!(
+        *seen.borrow(),
+        vec![(1, 2), (2, 4)],
+        "Effect should run once and see the updated memo."
+    );
+}
+
+#[test]
+fn writes_made_by_effects_are_flushed() {
+    let (count, set_count) = create_signal(0);
+    let (mirror, set_mirror) = create_signal(0);
+    create_effect(move || set_mirror(count()));
+
+    set_count(7);
+    assert_eq!(mirror(), 7);
+}
+
+#[test]
+fn batch_returns_the_closure_result() {
+    assert_eq!(batch(|| 42), 42);
+}
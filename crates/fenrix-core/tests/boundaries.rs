@@ -0,0 +1,104 @@
+mod common;
+
+use common::Request;
+use fenrix_core::{
+    create_effect, create_resource, create_root, create_signal, throw_error, ErrorBoundaryContext,
+    SuspenseContext,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+type Response = Result<u32, String>;
+
+#[test]
+fn errors_are_caught_by_the_nearest_boundary() {
+    create_root(|| {
+        let outer = ErrorBoundaryContext::new();
+        let inner = ErrorBoundaryContext::new();
+        outer.run(|| inner.run(|| throw_error("inner failure")));
+
+        assert_eq!(inner.error().unwrap().to_string(), "inner failure");
+        assert!(outer.error().is_none());
+
+        inner.reset();
+        assert!(inner.error().is_none());
+    });
+}
+
+#[test]
+fn errors_thrown_in_effects_reach_the_boundary() {
+    create_root(|| {
+        let boundary = ErrorBoundaryContext::new();
+        let (value, set_value) = create_signal(1);
+        boundary.run(|| {
+            create_effect(move || {
+                if value() < 0 {
+                    throw_error(format!("negative value: {}", value()));
+                }
+            });
+        });
+
+        assert!(boundary.error().is_none());
+        set_value(-1);
+        assert_eq!(boundary.error().unwrap().to_string(), "negative value: -1");
+    });
+}
+
+#[test]
+#[should_panic(expected = "no error boundary")]
+fn errors_outside_of_a_boundary_panic() {
+    throw_error("nobody is listening");
+}
+
+#[test]
+fn suspense_is_pending_until_resources_read_inside_resolve() {
+    create_root(|| {
+        let request = Request::<Response>::new();
+        let suspense = SuspenseContext::new();
+        let resource = create_resource(|| (), {
+            let request = request.clone();
+            move |_| request.clone()
+        });
+
+        assert!(
+            !suspense.is_pending(),
+            "A resource that is never read inside the boundary is not waited for."
+        );
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        suspense.run(|| {
+            let seen = Rc::clone(&seen);
+            create_effect(move || seen.borrow_mut().push(resource.data()));
+        });
+        assert!(suspense.is_pending());
+
+        request.resolve(Ok(7));
+        assert!(!suspense.is_pending());
+        assert_eq!(*seen.borrow(), vec![None, Some(7)]);
+    });
+}
+
+#[test]
+fn reading_a_failed_resource_throws_to_the_boundary() {
+    create_root(|| {
+        let request = Request::<Response>::new();
+        let resource = create_resource(|| (), {
+            let request = request.clone();
+            move |_| request.clone()
+        });
+        let boundary = ErrorBoundaryContext::new();
+        boundary.run(|| {
+            create_effect(move || {
+                resource.read();
+            });
+        });
+
+        request.resolve(Err("User not found".to_string()));
+        assert_eq!(boundary.error().unwrap().to_string(), "User not found");
+
+        // The old error is not thrown again while a refetch is loading.
+        boundary.reset();
+        resource.refetch();
+        assert!(boundary.error().is_none());
+    });
+}
@@ -0,0 +1,51 @@
+//! Helpers shared by the integration tests.
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+// A future that stays pending until the test resolves it.
+struct Deferred<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+#[derive(Clone)]
+pub struct Request<T>(Rc<RefCell<Deferred<T>>>);
+
+impl<T> Request<T> {
+    pub fn new() -> Self {
+        Request(Rc::new(RefCell::new(Deferred {
+            value: None,
+            waker: None,
+        })))
+    }
+
+    pub fn resolve(&self, value: T) {
+        let waker = {
+            let mut deferred = self.0.borrow_mut();
+            deferred.value = Some(value);
+            deferred.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for Request<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut deferred = self.0.borrow_mut();
+        match deferred.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                deferred.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use fenrix_core::{
+    create_root, create_scope, inject, inject_optional, provide_context, provide_factory,
+    provide_service, provide_service_as, provide_transient, try_inject, use_context,
+    with_component_context, DiError,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq)]
+struct Logger(&'static str);
+
+#[test]
+fn nested_providers_shadow_outer_ones() {
+    create_root(|| {
+        provide_context(Logger("app"));
+        with_component_context(|| {
+            assert_eq!(*inject::<Logger>(), Logger("app"));
+            with_component_context(|| {
+                provide_context(Logger("admin"));
+                with_component_context(|| {
+                    assert_eq!(*inject::<Logger>(), Logger("admin"));
+                });
+            });
+            // A sibling subtree still sees the outer provider.
+            with_component_context(|| {
+                assert_eq!(*inject::<Logger>(), Logger("app"));
+            });
+        });
+    });
+}
+
+#[test]
+fn the_global_container_is_the_root() {
+    struct Config(u32);
+    provide_service(Config(1));
+    create_root(|| {
+        assert_eq!(inject::<Config>().0, 1);
+        create_scope(|| {
+            provide_context(Config(2));
+            assert_eq!(inject::<Config>().0, 2);
+        });
+        assert_eq!(inject::<Config>().0, 1);
+    });
+}
+
+#[test]
+fn providers_do_not_leak_out_of_their_root() {
+    struct Tenant;
+    create_root(|| provide_context(Tenant));
+    create_root(|| assert!(use_context::<Tenant>().is_none()));
+}
+
+#[test]
+fn providers_are_dropped_with_their_scope() {
+    let (logger, scope) = create_scope(|| {
+        provide_context(Logger("scoped"));
+        use_context::<Logger>().unwrap()
+    });
+    let weak = Rc::downgrade(&logger);
+    drop(logger);
+    assert!(weak.upgrade().is_some());
+    scope.dispose();
+    assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn try_inject_names_the_missing_and_registered_types() {
+    #[derive(Debug)]
+    struct Missing;
+    create_root(|| {
+        provide_context(Logger("app"));
+        let error = try_inject::<Missing>().unwrap_err();
+        let DiError::NotProvided {
+            type_name,
+            registered,
+        } = &error
+        else {
+            panic!("unexpected error: {:?}", error);
+        };
+        assert!(type_name.ends_with("Missing"));
+        assert!(registered.contains(&std::any::type_name::<Logger>()));
+        assert!(error.to_string().contains("di::Logger"));
+        assert!(inject_optional::<Missing>().is_none());
+        assert_eq!(*try_inject::<Logger>().unwrap(), Logger("app"));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Service not provided: `di::")]
+fn inject_panics_with_the_missing_type() {
+    struct Missing;
+    create_root(|| {
+        inject::<Missing>();
+    });
+}
+
+trait Greeter {
+    fn greet(&self) -> String;
+}
+
+struct English;
+
+impl Greeter for English {
+    fn greet(&self) -> String {
+        "hello".to_string()
+    }
+}
+
+#[test]
+fn services_can_be_provided_as_trait_objects() {
+    provide_service_as::<dyn Greeter>(Rc::new(English));
+    assert_eq!(inject::<dyn Greeter>().greet(), "hello");
+}
+
+#[test]
+fn singleton_factories_run_once_on_first_injection() {
+    let calls = Rc::new(Cell::new(0));
+    provide_service(Logger("deps"));
+    provide_factory({
+        let calls = Rc::clone(&calls);
+        move || {
+            calls.set(calls.get() + 1);
+            Rc::new(format!("uses {}", inject::<Logger>().0))
+        }
+    });
+    assert_eq!(calls.get(), 0);
+    let first = inject::<String>();
+    let second = inject::<String>();
+    assert_eq!(*first, "uses deps");
+    assert!(Rc::ptr_eq(&first, &second));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn transient_factories_run_for_every_injection() {
+    provide_transient::<dyn Greeter>(|| Rc::new(English));
+    let first = inject::<dyn Greeter>();
+    let second = inject::<dyn Greeter>();
+    assert!(!Rc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn factory_cycles_are_detected() {
+    struct A;
+    let error = Rc::new(RefCell::new(None));
+    provide_factory({
+        let error = Rc::clone(&error);
+        move || {
+            *error.borrow_mut() = try_inject::<A>().err();
+            Rc::new(A)
+        }
+    });
+    inject::<A>();
+    let Some(DiError::Cycle { path }) = error.take() else {
+        panic!("expected a cycle");
+    };
+    assert_eq!(path.len(), 2);
+    assert!(path.iter().all(|name| name.ends_with("::A")));
+}
+
+#[test]
+#[should_panic(expected = "Dependency cycle detected")]
+fn inject_panics_on_a_cycle() {
+    struct A;
+    struct B;
+    provide_factory(|| {
+        inject::<B>();
+        Rc::new(A)
+    });
+    provide_factory(|| {
+        inject::<A>();
+        Rc::new(B)
+    });
+    inject::<A>();
+}
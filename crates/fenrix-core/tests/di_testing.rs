@@ -0,0 +1,98 @@
+use fenrix_core::{
+    create_scope, inject, inject_optional, override_service, override_service_as, provide_context,
+    provide_factory, provide_service, provide_service_as, with_test_container,
+};
+use std::rc::Rc;
+
+trait Api {
+    fn fetch(&self) -> String;
+}
+
+struct HttpApi;
+
+impl Api for HttpApi {
+    fn fetch(&self) -> String {
+        "http".to_string()
+    }
+}
+
+struct MockApi;
+
+impl Api for MockApi {
+    fn fetch(&self) -> String {
+        "mock".to_string()
+    }
+}
+
+struct Greeting(String);
+
+#[test]
+fn test_containers_start_empty_and_are_restored() {
+    provide_service(Greeting("global".to_string()));
+    with_test_container(|| {
+        assert!(inject_optional::<Greeting>().is_none());
+        provide_service(Greeting("test".to_string()));
+        assert_eq!(inject::<Greeting>().0, "test");
+    });
+    assert_eq!(inject::<Greeting>().0, "global");
+}
+
+#[test]
+fn test_containers_are_restored_after_a_panic() {
+    provide_service(Greeting("global".to_string()));
+    let result = std::panic::catch_unwind(|| {
+        with_test_container(|| {
+            provide_service(Greeting("test".to_string()));
+            panic!("test failed");
+        })
+    });
+    assert!(result.is_err());
+    assert_eq!(inject::<Greeting>().0, "global");
+}
+
+#[test]
+fn overrides_last_as_long_as_their_scope() {
+    with_test_container(|| {
+        provide_service_as::<dyn Api>(Rc::new(HttpApi));
+        let ((), scope) = create_scope(|| {
+            override_service_as::<dyn Api>(Rc::new(MockApi));
+            assert_eq!(inject::<dyn Api>().fetch(), "mock");
+        });
+        assert_eq!(inject::<dyn Api>().fetch(), "mock");
+        scope.dispose();
+        assert_eq!(inject::<dyn Api>().fetch(), "http");
+    });
+}
+
+#[test]
+fn overrides_win_over_scoped_providers_and_reach_factories() {
+    with_test_container(|| {
+        provide_service_as::<dyn Api>(Rc::new(HttpApi));
+        provide_factory(|| Rc::new(Greeting(inject::<dyn Api>().fetch())));
+        let ((), scope) = create_scope(|| {
+            provide_context(Greeting("scoped".to_string()));
+            override_service(Greeting("overridden".to_string()));
+            assert_eq!(inject::<Greeting>().0, "overridden");
+        });
+        scope.dispose();
+
+        override_service_as::<dyn Api>(Rc::new(MockApi));
+        assert_eq!(inject::<Greeting>().0, "mock");
+    });
+}
+
+#[test]
+fn nested_overrides_are_undone_in_order() {
+    with_test_container(|| {
+        provide_service(Greeting("base".to_string()));
+        let ((), outer) = create_scope(|| {
+            override_service(Greeting("outer".to_string()));
+            let ((), inner) = create_scope(|| override_service(Greeting("inner".to_string())));
+            assert_eq!(inject::<Greeting>().0, "inner");
+            inner.dispose();
+            assert_eq!(inject::<Greeting>().0, "outer");
+        });
+        outer.dispose();
+        assert_eq!(inject::<Greeting>().0, "base");
+    });
+}
@@ -0,0 +1,94 @@
+use fenrix_core::{create_effect, create_signal};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn effect_stops_tracking_signals_it_no_longer_reads() {
+    let (show_detail, set_show_detail) = create_signal(true);
+    let (detail, set_detail) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        create_effect(move || {
+            runs.set(runs.get() + 1);
+            if show_detail() {
+                detail();
+            }
+        });
+    }
+    assert_eq!(runs.get(), 1);
+
+    set_detail(1);
+    assert_eq!(
+        runs.get(),
+        2,
+        "Effect should track a conditionally read signal."
+    );
+
+    set_show_detail(false);
+    assert_eq!(runs.get(), 3);
+
+    set_detail(2);
+    assert_eq!(
+        runs.get(),
+        3,
+        "Effect should no longer be subscribed to `detail`."
+    );
+}
+
+#[test]
+fn effect_picks_up_new_dependencies() {
+    let (use_b, set_use_b) = create_signal(false);
+    let (b, set_b) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        create_effect(move || {
+            runs.set(runs.get() + 1);
+            if use_b() {
+                b();
+            }
+        });
+    }
+
+    set_b(1);
+    assert_eq!(runs.get(), 1, "`b` is not read yet.");
+
+    set_use_b(true);
+    set_b(2);
+    assert_eq!(runs.get(), 3);
+}
+
+#[test]
+fn nested_effects_are_dropped_when_their_parent_reruns() {
+    let (outer, set_outer) = create_signal(0);
+    let (inner, set_inner) = create_signal(0);
+    let inner_runs = Rc::new(Cell::new(0));
+    {
+        let inner_runs = Rc::clone(&inner_runs);
+        create_effect(move || {
+            outer();
+            let inner = inner.clone();
+            let inner_runs = Rc::clone(&inner_runs);
+            create_effect(move || {
+                inner();
+                inner_runs.set(inner_runs.get() + 1);
+            });
+        });
+    }
+    assert_eq!(inner_runs.get(), 1);
+
+    set_outer(1);
+    assert_eq!(
+        inner_runs.get(),
+        2,
+        "Re-running the parent creates a fresh child."
+    );
+
+    set_inner(1);
+    assert_eq!(
+        inner_runs.get(),
+        3,
+        "Only the current child should be notified; the old one was dropped."
+    );
+}
@@ -0,0 +1,84 @@
+use fenrix_core::{batch, create_effect, create_history_signal};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn undo_and_redo_walk_through_the_history() {
+    let text = create_history_signal(String::new(), 10);
+    text.set("a".to_string());
+    text.set("ab".to_string());
+    text.update(|t| t.push('c'));
+
+    assert!(text.undo());
+    assert_eq!(text.get(), "ab");
+    assert!(text.undo());
+    assert!(text.undo());
+    assert_eq!(text.get(), "");
+    assert!(!text.undo());
+
+    assert!(text.redo());
+    assert!(text.redo());
+    assert_eq!(text.get(), "ab");
+}
+
+#[test]
+fn a_new_change_discards_the_redo_entries() {
+    let count = create_history_signal(0, 10);
+    count.set(1);
+    count.set(2);
+    count.undo();
+    assert!(count.can_redo());
+    count.set(5);
+    assert!(!count.can_redo());
+    count.undo();
+    assert_eq!(count.get(), 1);
+}
+
+#[test]
+fn writes_inside_a_batch_are_one_entry() {
+    let count = create_history_signal(0, 10);
+    batch(|| {
+        count.set(1);
+        count.set(2);
+        count.update(|c| *c += 1);
+    });
+    count.set(4);
+
+    count.undo();
+    assert_eq!(count.get(), 3);
+    count.undo();
+    assert_eq!(count.get(), 0);
+    assert!(!count.can_undo());
+}
+
+#[test]
+fn the_oldest_entries_are_forgotten_past_the_capacity() {
+    let count = create_history_signal(0, 2);
+    for i in 1..=4 {
+        count.set(i);
+    }
+    assert!(count.undo());
+    assert!(count.undo());
+    assert!(!count.undo());
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn can_undo_and_can_redo_are_reactive() {
+    let count = create_history_signal(0, 10);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    create_effect({
+        let seen = Rc::clone(&seen);
+        move || {
+            seen.borrow_mut()
+                .push((count.get(), count.can_undo(), count.can_redo()))
+        }
+    });
+
+    count.set(1);
+    count.undo();
+    assert_eq!(
+        *seen.borrow(),
+        vec![(0, false, false), (1, true, false), (0, false, true)]
+    );
+}
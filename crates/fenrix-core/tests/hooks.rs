@@ -0,0 +1,175 @@
+use fenrix_core::{create_root, use_effect, use_reducer, use_state, ComponentInstance, HookError};
+use fenrix_macros::component;
+use std::cell::Cell;
+use std::rc::Rc;
+
+fn counter(show_extra: bool) -> i32 {
+    let (count, set_count) = use_state(|| 0);
+    if show_extra {
+        let _ = use_state(|| "extra");
+    }
+    set_count(count() + 1);
+    count()
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Clicks() -> i32 {
+    counter(false)
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Toggle(show_extra: bool) -> i32 {
+    counter(show_extra)
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Panel() -> (i32, i32) {
+    let clicks = Clicks();
+    (clicks, counter(false))
+}
+
+#[test]
+fn state_persists_across_renders() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Counter");
+        assert_eq!(instance.render(|| counter(false)), 1);
+        assert_eq!(instance.render(|| counter(false)), 2);
+        assert_eq!(instance.render(|| counter(false)), 3);
+    });
+}
+
+#[test]
+fn components_render_into_the_instance_that_renders_them() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Instance");
+        assert_eq!(instance.render(Clicks), 1);
+        assert_eq!(instance.render(Clicks), 2);
+
+        // Components rendered by a component get an instance of their own
+        // each time.
+        let panel = ComponentInstance::new("Panel");
+        assert_eq!(panel.render(Panel), (1, 1));
+        assert_eq!(panel.render(Panel), (1, 2));
+    });
+}
+
+#[test]
+fn effects_are_only_created_on_the_first_render() {
+    create_root(|| {
+        let runs = Rc::new(Cell::new(0));
+        let instance = ComponentInstance::new("Effect");
+        for _ in 0..3 {
+            instance.render(|| {
+                let runs = Rc::clone(&runs);
+                use_effect(move || runs.set(runs.get() + 1));
+            });
+        }
+        assert_eq!(runs.get(), 1);
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn a_moved_hook_is_reported_with_both_call_sites() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Form");
+        instance
+            .try_render(|| {
+                let _ = use_state(|| 0);
+                let _ = use_reducer(0, |state: &i32, n: i32| state + n);
+            })
+            .unwrap();
+
+        let error = instance
+            .try_render(|| {
+                let _ = use_reducer(0, |state: &i32, n: i32| state + n);
+                let _ = use_state(|| 0);
+            })
+            .unwrap_err();
+
+        let HookError::OrderChanged {
+            component,
+            index,
+            expected,
+            found,
+        } = error
+        else {
+            panic!("unexpected error: {:?}", error);
+        };
+        assert_eq!(component, "Form");
+        assert_eq!(index, 0);
+        assert_eq!(expected.hook, "use_state");
+        assert_eq!(found.hook, "use_reducer");
+        assert_eq!(expected.location.file(), file!());
+        assert!(found.location.line() > expected.location.line());
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn a_conditional_hook_changes_the_count() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Counter");
+        instance.try_render(|| counter(true)).unwrap();
+        let error = instance.try_render(|| counter(false)).unwrap_err();
+        assert_eq!(
+            error,
+            HookError::CountChanged {
+                component: "Counter",
+                expected: 2,
+                found: 1,
+            }
+        );
+        // The shorter render is what the next one is compared against.
+        assert_eq!(instance.try_render(|| counter(false)), Ok(3));
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn hook_errors_name_the_component() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Instance");
+        let render = |show_extra| Toggle(ToggleProps::builder().show_extra(show_extra).build());
+        instance.try_render(|| render(true)).unwrap();
+        assert_eq!(
+            instance.try_render(|| render(false)),
+            Err(HookError::CountChanged {
+                component: "Toggle",
+                expected: 2,
+                found: 1,
+            })
+        );
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "Hook order changed in `Counter`")]
+fn render_panics_on_a_moved_hook() {
+    create_root(|| {
+        let instance = ComponentInstance::new("Counter");
+        instance.render(|| {
+            let _ = use_state(|| 0);
+        });
+        instance.render(|| {
+            let _ = use_state(|| 0);
+        });
+    });
+}
+
+#[test]
+fn hooks_outside_a_component_are_an_error() {
+    let error = std::panic::catch_unwind(|| {
+        let _ = use_state(|| 0);
+    })
+    .unwrap_err();
+    let message = error.downcast_ref::<String>().unwrap();
+    assert_eq!(
+        *message,
+        HookError::OutsideComponent { hook: "use_state" }.to_string()
+    );
+}
@@ -0,0 +1,71 @@
+use fenrix_core::{inject, inject_optional, provide_service_as, DiError, Module};
+use fenrix_macros::{injectable, module};
+use std::cell::Cell;
+use std::rc::Rc;
+
+trait Logger {
+    fn prefix(&self) -> &str;
+}
+
+struct ConsoleLogger;
+
+impl Logger for ConsoleLogger {
+    fn prefix(&self) -> &str {
+        "console"
+    }
+}
+
+#[injectable]
+struct Config;
+
+#[injectable]
+struct ApiClient {
+    config: Rc<Config>,
+    logger: Rc<dyn Logger>,
+    requests: Cell<u32>,
+}
+
+#[injectable]
+struct AuthService(Rc<ApiClient>);
+
+#[module(AuthService, ApiClient, Config)]
+struct AppModule;
+
+#[test]
+fn modules_wire_services_in_any_order() {
+    provide_service_as::<dyn Logger>(Rc::new(ConsoleLogger));
+    AppModule::provide().unwrap();
+
+    let auth = inject::<AuthService>();
+    assert!(Rc::ptr_eq(&auth.0, &inject::<ApiClient>()));
+    assert!(Rc::ptr_eq(&auth.0.config, &inject::<Config>()));
+    assert_eq!(auth.0.logger.prefix(), "console");
+    assert_eq!(auth.0.requests.get(), 0);
+}
+
+#[test]
+fn missing_dependencies_are_reported_at_registration() {
+    let error = AppModule::provide().unwrap_err();
+    let DiError::MissingDependency {
+        service,
+        dependency,
+    } = error
+    else {
+        panic!("unexpected error: {:?}", error);
+    };
+    assert!(service.ends_with("ApiClient"));
+    assert!(dependency.ends_with("dyn injectable::Logger"));
+    // Nothing was provided.
+    assert!(inject_optional::<Config>().is_none());
+}
+
+#[test]
+fn modules_can_mix_in_ready_made_services() {
+    provide_service_as::<dyn Logger>(Rc::new(ConsoleLogger));
+    Module::new()
+        .injectable::<ApiClient>()
+        .service(Config)
+        .provide()
+        .unwrap();
+    assert_eq!(inject::<ApiClient>().logger.prefix(), "console");
+}
@@ -0,0 +1,82 @@
+use fenrix_core::{create_effect, create_memo, create_signal};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn memo_computes_initial_value() {
+    let (count, _) = create_signal(2);
+    let doubled = create_memo(move || count() * 2);
+    assert_eq!(doubled(), 4, "Memo should be computed on creation.");
+}
+
+#[test]
+fn memo_recomputes_when_dependency_changes() {
+    let (count, set_count) = create_signal(2);
+    let runs = Rc::new(Cell::new(0));
+    let doubled = {
+        let runs = Rc::clone(&runs);
+        create_memo(move || {
+            runs.set(runs.get() + 1);
+            count() * 2
+        })
+    };
+
+    assert_eq!(doubled(), 4);
+    assert_eq!(doubled(), 4);
+    assert_eq!(runs.get(), 1, "Reading a memo should not recompute it.");
+
+    set_count(5);
+    assert_eq!(
+        doubled(),
+        10,
+        "Memo should reflect the new dependency value."
+    );
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn memo_skips_notification_when_value_is_unchanged() {
+    let (count, set_count) = create_signal(1);
+    let is_even = create_memo(move || count() % 2 == 0);
+
+    let effect_runs = Rc::new(Cell::new(0));
+    {
+        let effect_runs = Rc::clone(&effect_runs);
+        let is_even = is_even.clone();
+        create_effect(move || {
+            is_even();
+            effect_runs.set(effect_runs.get() + 1);
+        });
+    }
+    assert_eq!(effect_runs.get(), 1);
+
+    set_count(3);
+    assert_eq!(
+        effect_runs.get(),
+        1,
+        "Effect should not re-run when the memo value is equal."
+    );
+
+    set_count(4);
+    assert_eq!(
+        effect_runs.get(),
+        2,
+        "Effect should re-run when the memo value changes."
+    );
+    assert!(is_even());
+}
+
+#[test]
+fn memos_can_depend_on_other_memos() {
+    let (count, set_count) = create_signal(1);
+    let doubled = create_memo(move || count() * 2);
+    let quadrupled = {
+        let doubled = doubled.clone();
+        create_memo(move || doubled() * 2)
+    };
+
+    assert_eq!(quadrupled(), 4);
+    set_count(3);
+    assert_eq!(doubled(), 6);
+    assert_eq!(quadrupled(), 12);
+}
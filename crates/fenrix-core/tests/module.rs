@@ -0,0 +1,41 @@
+use fenrix_core::{
+    create_scope, inject, provide_context, provide_service, with_test_container, DiError, Module,
+};
+use fenrix_macros::injectable;
+use std::rc::Rc;
+
+struct Database(&'static str);
+
+#[injectable]
+struct Repository(Rc<Database>);
+
+#[test]
+fn scoped_providers_do_not_satisfy_module_dependencies() {
+    with_test_container(|| {
+        let ((), scope) = create_scope(|| {
+            // The factory of `Repository` runs outside of this scope, where
+            // the database is not visible.
+            provide_context(Database("scoped"));
+            let result = Module::new().injectable::<Repository>().provide();
+            let Err(DiError::MissingDependency {
+                service,
+                dependency,
+            }) = result
+            else {
+                panic!("expected a missing dependency, got {:?}", result);
+            };
+            assert!(service.ends_with("Repository"));
+            assert!(dependency.ends_with("Database"));
+        });
+        scope.dispose();
+    });
+}
+
+#[test]
+fn global_services_satisfy_module_dependencies() {
+    with_test_container(|| {
+        provide_service(Database("global"));
+        Module::new().injectable::<Repository>().provide().unwrap();
+        assert_eq!(inject::<Repository>().0 .0, "global");
+    });
+}
@@ -0,0 +1,118 @@
+use fenrix_core::{create_effect, create_memo, create_signal, with_component_context};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[test]
+fn outer_effect_keeps_tracking_after_creating_a_nested_effect() {
+    let (before, set_before) = create_signal(0);
+    let (after, set_after) = create_signal(0);
+    let outer_runs = Rc::new(Cell::new(0));
+    {
+        let outer_runs = Rc::clone(&outer_runs);
+        create_effect(move || {
+            before();
+            create_effect(|| {});
+            after();
+            outer_runs.set(outer_runs.get() + 1);
+        });
+    }
+
+    set_before(1);
+    assert_eq!(outer_runs.get(), 2);
+    set_after(1);
+    assert_eq!(
+        outer_runs.get(),
+        3,
+        "Reads after a nested effect should still be tracked by the outer effect."
+    );
+}
+
+#[test]
+fn nested_effect_reads_are_not_tracked_by_the_outer_effect() {
+    let (outer, set_outer) = create_signal(0);
+    let (inner, set_inner) = create_signal(0);
+    let outer_runs = Rc::new(Cell::new(0));
+    let inner_runs = Rc::new(Cell::new(0));
+    {
+        let outer_runs = Rc::clone(&outer_runs);
+        let inner_runs = Rc::clone(&inner_runs);
+        create_effect(move || {
+            outer();
+            outer_runs.set(outer_runs.get() + 1);
+            let inner = inner.clone();
+            let inner_runs = Rc::clone(&inner_runs);
+            create_effect(move || {
+                inner();
+                inner_runs.set(inner_runs.get() + 1);
+            });
+        });
+    }
+
+    set_inner(1);
+    assert_eq!(
+        outer_runs.get(),
+        1,
+        "The outer effect should not track the inner read."
+    );
+    assert_eq!(inner_runs.get(), 2);
+
+    set_outer(1);
+    assert_eq!(outer_runs.get(), 2);
+}
+
+#[test]
+fn memo_created_inside_an_effect_tracks_its_own_reads() {
+    let (count, set_count) = create_signal(1);
+    let (trigger, set_trigger) = create_signal(0);
+    let outer_runs = Rc::new(Cell::new(0));
+    let seen = Rc::new(Cell::new(0));
+    {
+        let outer_runs = Rc::clone(&outer_runs);
+        let seen = Rc::clone(&seen);
+        create_effect(move || {
+            trigger();
+            outer_runs.set(outer_runs.get() + 1);
+            let count = count.clone();
+            let doubled = create_memo(move || count() * 2);
+            let seen = Rc::clone(&seen);
+            create_effect(move || seen.set(doubled()));
+        });
+    }
+    assert_eq!(seen.get(), 2);
+
+    set_count(5);
+    assert_eq!(seen.get(), 10);
+    assert_eq!(
+        outer_runs.get(),
+        1,
+        "Memo reads should not leak into the outer effect."
+    );
+
+    set_trigger(1);
+    assert_eq!(outer_runs.get(), 2);
+}
+
+#[test]
+fn component_reads_do_not_subscribe_the_rendering_effect() {
+    let (route, set_route) = create_signal(0);
+    let (internal, set_internal) = create_signal(0);
+    let renders = Rc::new(Cell::new(0));
+    {
+        let renders = Rc::clone(&renders);
+        create_effect(move || {
+            route();
+            renders.set(renders.get() + 1);
+            with_component_context(internal.clone());
+        });
+    }
+
+    set_internal(1);
+    assert_eq!(
+        renders.get(),
+        1,
+        "Component-internal reads should not re-render the parent."
+    );
+
+    set_route(1);
+    assert_eq!(renders.get(), 2);
+}
@@ -0,0 +1,131 @@
+use fenrix_core::{
+    batch, create_persisted_signal, create_persisted_signal_in, create_root, provide_service_as,
+    MemoryStorage, Storage,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    theme: String,
+    font_size: u32,
+}
+
+fn defaults() -> Settings {
+    Settings {
+        theme: "light".to_string(),
+        font_size: 14,
+    }
+}
+
+thread_local! {
+    static LOADS: Cell<usize> = const { Cell::new(0) };
+}
+
+// A number that counts how often it is read from the storage.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct Counted(u32);
+
+impl<'de> Deserialize<'de> for Counted {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        LOADS.with(|loads| loads.set(loads.get() + 1));
+        u32::deserialize(deserializer).map(Counted)
+    }
+}
+
+#[test]
+fn starts_with_the_default_and_saves_changes() {
+    let storage = Rc::new(MemoryStorage::new());
+    create_root(|| {
+        let settings = create_persisted_signal_in(storage.clone(), "settings", defaults());
+        assert_eq!(settings.get(), defaults());
+        assert_eq!(storage.get("settings"), None);
+
+        settings.update(|s| s.font_size = 16);
+        let saved: Settings = serde_json::from_str(&storage.get("settings").unwrap()).unwrap();
+        assert_eq!(saved.font_size, 16);
+    });
+}
+
+#[test]
+fn loads_the_stored_value() {
+    let storage = Rc::new(MemoryStorage::new());
+    storage.set("count", "41");
+    storage.set("broken", "not json");
+    create_root(|| {
+        assert_eq!(
+            create_persisted_signal_in(storage.clone(), "count", 0).get(),
+            41
+        );
+        assert_eq!(
+            create_persisted_signal_in(storage.clone(), "broken", 7).get(),
+            7
+        );
+    });
+}
+
+#[test]
+fn follows_changes_from_other_tabs() {
+    let storage = Rc::new(MemoryStorage::new());
+    let ((), scope) = create_root(|| {
+        let count = create_persisted_signal_in(storage.clone(), "count", Counted(0));
+        storage.set_from_other_tab("count", Some("5"));
+        assert_eq!(count.get(), Counted(5));
+        storage.set_from_other_tab("other", Some("9"));
+        assert_eq!(count.get(), Counted(5));
+        storage.set_from_other_tab("count", None);
+        assert_eq!(count.get(), Counted(0));
+    });
+    assert_eq!(LOADS.with(Cell::get), 1);
+
+    // Once the scope is disposed, changes are no longer read.
+    scope.dispose();
+    storage.set_from_other_tab("count", Some("6"));
+    assert_eq!(LOADS.with(Cell::get), 1);
+}
+
+#[test]
+fn outside_changes_are_not_written_back() {
+    let storage = Rc::new(MemoryStorage::new());
+    create_root(|| {
+        let count = create_persisted_signal_in(storage.clone(), "count", 0);
+        count.set(3);
+        assert_eq!(storage.get("count").as_deref(), Some("3"));
+
+        // A removal resets the signal but leaves the key removed.
+        storage.set_from_other_tab("count", None);
+        assert_eq!(count.get(), 0);
+        assert_eq!(storage.get("count"), None);
+
+        // Unparseable JSON from another tab is left alone as well.
+        storage.set_from_other_tab("count", Some("\"nine\""));
+        assert_eq!(count.get(), 0);
+        assert_eq!(storage.get("count").as_deref(), Some("\"nine\""));
+
+        // Changes made afterwards are saved again.
+        count.set(4);
+        assert_eq!(storage.get("count").as_deref(), Some("4"));
+
+        // Inside a batch, the effect runs after the change has been applied.
+        batch(|| storage.set_from_other_tab("count", None));
+        assert_eq!(count.get(), 0);
+        assert_eq!(storage.get("count"), None);
+        batch(|| storage.set_from_other_tab("count", Some("8")));
+        assert_eq!(count.get(), 8);
+        assert_eq!(storage.get("count").as_deref(), Some("8"));
+    });
+}
+
+#[test]
+fn uses_the_injected_storage() {
+    let storage = Rc::new(MemoryStorage::new());
+    storage.set("name", "\"Ada\"");
+    provide_service_as::<dyn Storage>(storage.clone());
+    create_root(|| {
+        let name = create_persisted_signal("name", String::new());
+        assert_eq!(name.get(), "Ada");
+        name.set("Grace".to_string());
+    });
+    assert_eq!(storage.get("name").as_deref(), Some("\"Grace\""));
+}
@@ -0,0 +1,73 @@
+use fenrix_core::{component_props, use_state, Component};
+use fenrix_macros::component;
+use std::rc::Rc;
+
+#[allow(non_snake_case)]
+#[component]
+fn Greeting(
+    #[prop(into)] name: String,
+    #[prop(optional)] excited: bool,
+    #[prop(default = 1)] times: usize,
+) -> String {
+    let mark = if excited { "!" } else { "." };
+    format!("Hello, {}{}", name, mark).repeat(times)
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Section(title: &'static str, children: Rc<dyn Fn() -> String>) -> String {
+    format!("{}: {}", title, children())
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Counter(#[prop(default = 5)] start: i32) -> i32 {
+    let (count, _) = use_state(move || start);
+    count()
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn Plain() -> &'static str {
+    "plain"
+}
+
+#[test]
+fn unset_props_use_their_defaults() {
+    let props = GreetingProps::builder().name("Ada").build();
+    assert_eq!(props.name, "Ada");
+    assert!(!props.excited);
+    assert_eq!(props.times, 1);
+    assert_eq!(Greeting(props), "Hello, Ada.");
+}
+
+#[test]
+fn props_can_be_set_in_any_order() {
+    let props = GreetingProps::builder()
+        .times(2)
+        .excited(true)
+        .name(String::from("Grace"))
+        .build();
+    assert_eq!(Greeting(props), "Hello, Grace!Hello, Grace!");
+}
+
+#[test]
+fn children_are_a_render_closure() {
+    let props = SectionProps::builder()
+        .children(Rc::new(|| "body".to_string()))
+        .title("Intro")
+        .build();
+    assert_eq!(Section(props), "Intro: body");
+}
+
+#[test]
+fn components_are_called_through_their_props_builder() {
+    // This is how `rsx!` calls components, with or without props.
+    let props = component_props(&Counter).start(3).build();
+    assert_eq!(Component::call(Counter, props), 3);
+    assert_eq!(
+        Component::call(Counter, component_props(&Counter).build()),
+        5
+    );
+    assert_eq!(Component::call(Plain, ()), "plain");
+}
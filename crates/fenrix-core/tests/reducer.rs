@@ -0,0 +1,99 @@
+use fenrix_core::{
+    create_effect, use_reducer, use_reducer_with_middleware, with_component_context, Middleware,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq)]
+enum Action {
+    Increment,
+    Add(i32),
+    Undo,
+}
+
+fn reduce(count: &i32, action: Action) -> i32 {
+    match action {
+        Action::Increment => count + 1,
+        Action::Add(n) => count + n,
+        Action::Undo => *count,
+    }
+}
+
+#[test]
+fn dispatch_applies_the_reducer() {
+    with_component_context(|| {
+        let (count, dispatch) = use_reducer(0, reduce);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_effect({
+            let seen = Rc::clone(&seen);
+            move || seen.borrow_mut().push(count.get())
+        });
+
+        dispatch(Action::Increment);
+        dispatch(Action::Add(10));
+        assert_eq!(count.get(), 11);
+        assert_eq!(*seen.borrow(), vec![0, 1, 11]);
+    });
+}
+
+#[test]
+fn middleware_sees_every_action_in_order() {
+    with_component_context(|| {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let outer: Middleware<i32, Action> = Box::new({
+            let log = Rc::clone(&log);
+            move |state, action, next| {
+                log.borrow_mut()
+                    .push(format!("outer {:?} at {}", action, state));
+                next(action)
+            }
+        });
+        let inner: Middleware<i32, Action> = Box::new({
+            let log = Rc::clone(&log);
+            move |_, action, next| {
+                log.borrow_mut().push(format!("inner {:?}", action));
+                // Middleware may change the action before passing it on.
+                match action {
+                    Action::Increment => next(Action::Add(2)),
+                    action => next(action),
+                }
+            }
+        });
+
+        let (count, dispatch) = use_reducer_with_middleware(0, reduce, vec![outer, inner]);
+        dispatch(Action::Increment);
+        assert_eq!(count.get(), 2);
+        assert_eq!(
+            *log.borrow(),
+            vec!["outer Increment at 0", "inner Increment"]
+        );
+    });
+}
+
+#[test]
+fn middleware_can_implement_undo() {
+    with_component_context(|| {
+        let history = Rc::new(RefCell::new(Vec::new()));
+        let undo: Middleware<i32, Action> = Box::new(move |state, action, next| {
+            if action == Action::Undo {
+                return history.borrow_mut().pop().unwrap_or(*state);
+            }
+            history.borrow_mut().push(*state);
+            next(action)
+        });
+
+        let (count, dispatch) = use_reducer_with_middleware(0, reduce, vec![undo]);
+        dispatch(Action::Add(5));
+        dispatch(Action::Add(5));
+        dispatch(Action::Undo);
+        assert_eq!(count.get(), 5);
+        dispatch(Action::Undo);
+        assert_eq!(count.get(), 0);
+    });
+}
+
+#[test]
+#[should_panic(expected = "`use_reducer` can only be called inside a component.")]
+fn use_reducer_outside_of_a_component_panics() {
+    let _ = use_reducer(0, reduce);
+}
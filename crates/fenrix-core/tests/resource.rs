@@ -0,0 +1,157 @@
+mod common;
+
+use common::Request;
+use fenrix_core::{create_effect, create_resource, create_root, create_signal};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+type Requests = Rc<RefCell<Vec<(u32, Request<Result<String, String>>)>>>;
+
+// Records every request the fetcher makes so the test can resolve them.
+fn recording_fetcher(
+    requests: &Requests,
+) -> impl Fn(u32) -> Request<Result<String, String>> + 'static {
+    let requests = Rc::clone(requests);
+    move |id| {
+        let request = Request::new();
+        requests.borrow_mut().push((id, request.clone()));
+        request
+    }
+}
+
+#[test]
+fn resource_loads_data_for_its_source() {
+    let requests = Requests::default();
+    let (id, _) = create_signal(1);
+    let user = create_resource(id, recording_fetcher(&requests));
+
+    assert!(user.loading());
+    assert_eq!(user.data(), None);
+    assert_eq!(requests.borrow().len(), 1);
+
+    requests.borrow()[0].1.resolve(Ok("GV".to_string()));
+    assert!(!user.loading());
+    assert_eq!(user.data(), Some("GV".to_string()));
+    assert_eq!(user.error(), None);
+}
+
+#[test]
+fn resource_exposes_errors() {
+    let requests = Requests::default();
+    let (id, _) = create_signal(2);
+    let user = create_resource(id, recording_fetcher(&requests));
+
+    requests.borrow()[0]
+        .1
+        .resolve(Err("User not found".to_string()));
+    assert!(!user.loading());
+    assert_eq!(user.data(), None);
+    assert_eq!(user.error(), Some("User not found".to_string()));
+}
+
+#[test]
+fn changing_the_source_cancels_the_stale_request() {
+    let requests = Requests::default();
+    let (id, set_id) = create_signal(1);
+    let user = create_resource(id, recording_fetcher(&requests));
+
+    set_id(2);
+    assert_eq!(requests.borrow().len(), 2);
+    assert_eq!(requests.borrow()[1].0, 2);
+
+    requests.borrow()[1].1.resolve(Ok("second".to_string()));
+    requests.borrow()[0].1.resolve(Ok("first".to_string()));
+    assert_eq!(
+        user.data(),
+        Some("second".to_string()),
+        "A stale response should never overwrite a newer one."
+    );
+}
+
+#[test]
+fn refetch_starts_a_new_request_with_the_same_input() {
+    let requests = Requests::default();
+    let (id, _) = create_signal(1);
+    let user = create_resource(id, recording_fetcher(&requests));
+    requests.borrow()[0].1.resolve(Ok("old".to_string()));
+
+    user.refetch();
+    assert_eq!(requests.borrow().len(), 2);
+    assert_eq!(requests.borrow()[1].0, 1);
+    assert!(user.loading());
+    assert_eq!(
+        user.data(),
+        Some("old".to_string()),
+        "Data should stay available while refetching."
+    );
+
+    requests.borrow()[1].1.resolve(Ok("new".to_string()));
+    assert_eq!(user.data(), Some("new".to_string()));
+}
+
+#[test]
+fn ready_futures_resolve_immediately() {
+    let user = create_resource(|| 1, |id: u32| async move { Ok::<_, ()>(id * 10) });
+    assert!(!user.loading());
+    assert_eq!(user.data(), Some(10));
+}
+
+#[test]
+fn effects_rerun_when_a_resource_resolves() {
+    let requests = Requests::default();
+    let (id, _) = create_signal(1);
+    let user = create_resource(id, recording_fetcher(&requests));
+
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    create_effect({
+        let seen = Rc::clone(&seen);
+        move || seen.borrow_mut().push((user.loading(), user.data()))
+    });
+
+    requests.borrow()[0].1.resolve(Ok("GV".to_string()));
+    assert_eq!(
+        *seen.borrow(),
+        vec![(true, None), (false, Some("GV".to_string()))],
+        "Loading and data should change together."
+    );
+}
+
+#[test]
+fn disposing_the_scope_cancels_pending_requests() {
+    let requests = Requests::default();
+    let dropped = Rc::new(Cell::new(false));
+
+    struct SetOnDrop(Rc<Cell<bool>>);
+    impl Drop for SetOnDrop {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    let (_, root) = create_root({
+        let fetch = recording_fetcher(&requests);
+        let dropped = Rc::clone(&dropped);
+        move || {
+            create_resource(
+                || 1,
+                move |id| {
+                    let guard = SetOnDrop(Rc::clone(&dropped));
+                    let request = fetch(id);
+                    async move {
+                        let _guard = guard;
+                        request.await
+                    }
+                },
+            );
+        }
+    });
+
+    assert!(!dropped.get());
+    root.dispose();
+    assert!(
+        dropped.get(),
+        "The pending future should have been dropped."
+    );
+    // Resolving afterwards is harmless.
+    requests.borrow()[0].1.resolve(Ok("late".to_string()));
+}
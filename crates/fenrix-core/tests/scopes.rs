@@ -0,0 +1,122 @@
+use fenrix_core::{
+    create_effect, create_root, create_scope, create_signal, on_cleanup, with_component_context,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[test]
+fn disposing_a_root_stops_its_effects() {
+    let (count, set_count) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    let (_, root) = create_root({
+        let runs = Rc::clone(&runs);
+        move || {
+            create_effect(move || {
+                count();
+                runs.set(runs.get() + 1);
+            });
+        }
+    });
+
+    set_count(1);
+    assert_eq!(runs.get(), 2);
+
+    root.dispose();
+    assert!(root.is_disposed());
+    set_count(2);
+    assert_eq!(runs.get(), 2, "Disposed effects should not run again.");
+}
+
+#[test]
+fn cleanups_run_in_reverse_order_on_dispose() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let (_, root) = create_root({
+        let log = Rc::clone(&log);
+        move || {
+            let first = Rc::clone(&log);
+            on_cleanup(move || first.borrow_mut().push("first"));
+            let second = Rc::clone(&log);
+            on_cleanup(move || second.borrow_mut().push("second"));
+        }
+    });
+    assert!(log.borrow().is_empty());
+
+    root.dispose();
+    root.dispose();
+    assert_eq!(
+        *log.borrow(),
+        vec!["second", "first"],
+        "Cleanups should run once."
+    );
+}
+
+#[test]
+fn child_scopes_are_disposed_with_their_parent() {
+    let cleaned = Rc::new(Cell::new(false));
+    let ((_, child), root) = create_root({
+        let cleaned = Rc::clone(&cleaned);
+        move || create_scope(move || on_cleanup(move || cleaned.set(true)))
+    });
+
+    root.dispose();
+    assert!(cleaned.get());
+    assert!(child.is_disposed());
+}
+
+#[test]
+fn roots_are_not_owned_by_the_current_scope() {
+    let (inner_root, outer) = create_root(|| create_root(|| ()).1);
+    outer.dispose();
+    assert!(!inner_root.is_disposed());
+}
+
+#[test]
+fn effect_cleanups_run_before_each_rerun() {
+    let (count, set_count) = create_signal(0);
+    let log = Rc::new(RefCell::new(Vec::new()));
+    {
+        let log = Rc::clone(&log);
+        create_effect(move || {
+            let value = count();
+            log.borrow_mut().push(format!("run {}", value));
+            let log = Rc::clone(&log);
+            on_cleanup(move || log.borrow_mut().push(format!("cleanup {}", value)));
+        });
+    }
+
+    set_count(1);
+    assert_eq!(*log.borrow(), vec!["run 0", "cleanup 0", "run 1"]);
+}
+
+#[test]
+fn component_effects_are_torn_down_when_the_component_is_replaced() {
+    let (page, set_page) = create_signal(0);
+    let (clicks, set_clicks) = create_signal(0);
+    let component_runs = Rc::new(Cell::new(0));
+    {
+        let component_runs = Rc::clone(&component_runs);
+        // Stands in for the effect `rsx!` generates for a rendered node.
+        create_effect(move || {
+            page();
+            let clicks = clicks.clone();
+            let component_runs = Rc::clone(&component_runs);
+            with_component_context(move || {
+                create_effect(move || {
+                    clicks();
+                    component_runs.set(component_runs.get() + 1);
+                });
+            });
+        });
+    }
+    assert_eq!(component_runs.get(), 1);
+
+    set_page(1);
+    assert_eq!(component_runs.get(), 2);
+
+    set_clicks(1);
+    assert_eq!(
+        component_runs.get(),
+        3,
+        "Only the current component's effect should run."
+    );
+}
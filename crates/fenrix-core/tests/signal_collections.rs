@@ -0,0 +1,173 @@
+use fenrix_core::{
+    create_effect, create_root, create_signal_map, create_signal_vec, MapDiff, VecDiff,
+};
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+#[test]
+fn mutations_emit_diffs() {
+    let list = create_signal_vec(vec!["a", "b"]);
+    let diffs = Rc::new(RefCell::new(Vec::new()));
+    list.on_diff({
+        let diffs = Rc::clone(&diffs);
+        move |diff| diffs.borrow_mut().push(diff.clone())
+    });
+
+    list.push("c");
+    list.insert(0, "z");
+    list.set(1, "A");
+    list.move_item(0, 3);
+    assert_eq!(list.remove(0), "A");
+    assert_eq!(list.pop(), Some("z"));
+
+    assert_eq!(
+        *diffs.borrow(),
+        vec![
+            VecDiff::Push { value: "c" },
+            VecDiff::Insert {
+                index: 0,
+                value: "z"
+            },
+            VecDiff::Set {
+                index: 1,
+                value: "A"
+            },
+            VecDiff::Move { from: 0, to: 3 },
+            VecDiff::Remove { index: 0 },
+            VecDiff::Pop,
+        ]
+    );
+    assert_eq!(list.get(), vec!["b", "c"]);
+}
+
+#[test]
+fn applying_the_diffs_keeps_a_copy_in_sync() {
+    let list = create_signal_vec(vec![1, 2, 3]);
+    let mirror = Rc::new(RefCell::new(list.get()));
+    list.on_diff({
+        let mirror = Rc::clone(&mirror);
+        move |diff| diff.apply(&mut mirror.borrow_mut())
+    });
+
+    list.push(4);
+    list.move_item(3, 0);
+    list.set(2, 20);
+    list.remove(1);
+    list.insert(1, 10);
+    list.pop();
+    assert_eq!(*mirror.borrow(), list.get());
+
+    list.replace(vec![7, 8]);
+    list.clear();
+    list.push(9);
+    assert_eq!(*mirror.borrow(), vec![9]);
+}
+
+#[test]
+fn readers_are_notified_of_mutations() {
+    let list = create_signal_vec(Vec::new());
+    let lengths = Rc::new(RefCell::new(Vec::new()));
+    create_effect({
+        let lengths = Rc::clone(&lengths);
+        move || lengths.borrow_mut().push(list.len())
+    });
+
+    list.push(1);
+    list.push(2);
+    list.pop();
+    assert_eq!(*lengths.borrow(), vec![0, 1, 2, 1]);
+}
+
+#[test]
+fn listeners_see_the_diff_before_readers_run() {
+    let list = create_signal_vec(Vec::new());
+    let map = create_signal_map(BTreeMap::new());
+    let events = Rc::new(RefCell::new(Vec::new()));
+    list.on_diff({
+        let events = Rc::clone(&events);
+        move |_| events.borrow_mut().push("vec diff")
+    });
+    map.on_diff({
+        let events = Rc::clone(&events);
+        move |_| events.borrow_mut().push("map diff")
+    });
+    create_effect({
+        let events = Rc::clone(&events);
+        move || {
+            list.len();
+            map.len();
+            events.borrow_mut().push("effect");
+        }
+    });
+
+    list.push(1);
+    map.insert("a", 1);
+    assert_eq!(
+        *events.borrow(),
+        vec!["effect", "vec diff", "effect", "map diff", "effect"]
+    );
+}
+
+#[test]
+fn listeners_are_removed_with_their_scope() {
+    let list = create_signal_vec(Vec::new());
+    let calls = Rc::new(Cell::new(0));
+    let (_, scope) = create_root({
+        let calls = Rc::clone(&calls);
+        move || list.on_diff(move |_| calls.set(calls.get() + 1))
+    });
+
+    list.push(1);
+    scope.dispose();
+    list.push(2);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn map_mutations_emit_diffs() {
+    let map = create_signal_map(BTreeMap::new());
+    let diffs = Rc::new(RefCell::new(Vec::new()));
+    map.on_diff({
+        let diffs = Rc::clone(&diffs);
+        move |diff| diffs.borrow_mut().push(diff.clone())
+    });
+
+    assert_eq!(map.insert("a", 1), None);
+    assert_eq!(map.insert("a", 2), Some(1));
+    assert_eq!(
+        map.remove(&"b"),
+        None,
+        "Removing a missing key emits nothing."
+    );
+    assert_eq!(map.remove(&"a"), Some(2));
+
+    assert_eq!(
+        *diffs.borrow(),
+        vec![
+            MapDiff::Insert { key: "a", value: 1 },
+            MapDiff::Update { key: "a", value: 2 },
+            MapDiff::Remove { key: "a" },
+        ]
+    );
+}
+
+#[test]
+fn map_readers_are_notified_of_mutations() {
+    let map = create_signal_map(BTreeMap::from([(1, "one")]));
+    let mirror = Rc::new(RefCell::new(BTreeMap::new()));
+    map.on_diff({
+        let mirror = Rc::clone(&mirror);
+        move |diff| diff.apply(&mut mirror.borrow_mut())
+    });
+    let seen = Rc::new(RefCell::new(Vec::new()));
+    create_effect({
+        let seen = Rc::clone(&seen);
+        move || seen.borrow_mut().push(map.get(&2))
+    });
+
+    map.replace(BTreeMap::from([(1, "uno")]));
+    map.insert(2, "two");
+    assert_eq!(*seen.borrow(), vec![None, None, Some("two")]);
+    assert_eq!(*mirror.borrow(), map.with(Clone::clone));
+}
@@ -0,0 +1,72 @@
+use fenrix_core::{create_effect, create_root, create_rw_signal, ReadSignal, RwSignal};
+use std::cell::Cell;
+use std::rc::Rc;
+
+struct Counter {
+    count: RwSignal<i32>,
+}
+
+fn double(source: ReadSignal<i32>) -> i32 {
+    source.get() * 2
+}
+
+#[test]
+fn handles_are_copy_and_nameable() {
+    let counter = Counter {
+        count: create_rw_signal(1),
+    };
+    let copy = counter.count;
+    copy.set(3);
+    assert_eq!(counter.count.get(), 3);
+    assert_eq!(double(counter.count.read_only()), 6);
+}
+
+#[test]
+fn split_halves_share_the_same_value() {
+    let (read, write) = create_rw_signal("a".to_string()).split();
+    write.set("b".to_string());
+    assert_eq!(read.get(), "b");
+}
+
+#[test]
+fn update_mutates_in_place_and_notifies() {
+    let items = create_rw_signal(vec![1, 2]);
+    let seen_len = Rc::new(Cell::new(0));
+    {
+        let seen_len = Rc::clone(&seen_len);
+        create_effect(move || seen_len.set(items.with(Vec::len)));
+    }
+    assert_eq!(seen_len.get(), 2);
+
+    items.update(|items| items.push(3));
+    assert_eq!(seen_len.get(), 3);
+}
+
+#[test]
+fn with_borrows_values_that_are_not_clone() {
+    struct NotClone(u8);
+    let signal = create_rw_signal(NotClone(7));
+    assert_eq!(signal.with(|v| v.0), 7);
+    signal.update(|v| v.0 = 8);
+    assert_eq!(signal.with(|v| v.0), 8);
+}
+
+#[test]
+fn signals_are_released_with_their_scope() {
+    let (signal, root) = create_root(|| create_rw_signal(1));
+    root.dispose();
+
+    // Writes to a disposed signal are ignored.
+    signal.set(2);
+    let read = std::panic::catch_unwind(|| signal.get());
+    assert!(read.is_err(), "Reading a disposed signal should panic.");
+}
+
+#[test]
+fn reused_slots_do_not_alias_old_handles() {
+    let (old, root) = create_root(|| create_rw_signal(1));
+    root.dispose();
+    let new = create_rw_signal(2);
+    assert_eq!(new.get(), 2);
+    assert!(std::panic::catch_unwind(|| old.get()).is_err());
+}
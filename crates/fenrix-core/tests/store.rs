@@ -0,0 +1,169 @@
+use fenrix_core::{create_effect, create_store};
+use fenrix_macros::Store;
+use std::cell::Cell;
+use std::rc::Rc;
+
+#[derive(Clone, Debug, PartialEq, Store)]
+struct User {
+    name: String,
+    age: u32,
+    #[store]
+    address: Address,
+}
+
+#[derive(Clone, Debug, PartialEq, Store)]
+struct Address {
+    city: String,
+    street: String,
+}
+
+fn user() -> User {
+    User {
+        name: "GV".to_string(),
+        age: 30,
+        address: Address {
+            city: "Oslo".to_string(),
+            street: "Main St".to_string(),
+        },
+    }
+}
+
+// Counts the runs of an effect that reads a single value.
+fn count_runs(read: impl Fn() + 'static) -> Rc<Cell<u32>> {
+    let runs = Rc::new(Cell::new(0));
+    create_effect({
+        let runs = Rc::clone(&runs);
+        move || {
+            read();
+            runs.set(runs.get() + 1);
+        }
+    });
+    runs
+}
+
+#[test]
+fn fields_are_tracked_independently() {
+    let store = create_store(user());
+    let name_runs = count_runs(move || {
+        store.name().get();
+    });
+    let age_runs = count_runs(move || {
+        store.age().get();
+    });
+
+    store.age().update(|age| *age += 1);
+    assert_eq!(store.age().get(), 31);
+    assert_eq!(age_runs.get(), 2);
+    assert_eq!(
+        name_runs.get(),
+        1,
+        "Writing `age` should not re-run `name` readers."
+    );
+}
+
+#[test]
+fn nested_updates_only_notify_affected_readers() {
+    let store = create_store(user());
+    let city_runs = count_runs(move || {
+        store.address().city().get();
+    });
+    let street_runs = count_runs(move || {
+        store.address().street().get();
+    });
+
+    store.address().city().set("Bergen".to_string());
+    assert_eq!(store.address().city().get(), "Bergen");
+    assert_eq!(city_runs.get(), 2);
+    assert_eq!(street_runs.get(), 1);
+}
+
+#[test]
+fn snapshots_and_whole_value_updates() {
+    let store = create_store(user());
+    assert_eq!(store.get(), user());
+
+    let snapshot_runs = count_runs(move || {
+        store.get();
+    });
+    let mut moved = user();
+    moved.address.city = "Bergen".to_string();
+    store.set(moved.clone());
+
+    assert_eq!(store.get(), moved);
+    assert_eq!(
+        snapshot_runs.get(),
+        2,
+        "Replacing every field should re-run a snapshot reader once."
+    );
+}
+
+#[test]
+fn whole_value_updates_only_notify_changed_fields() {
+    let store = create_store(user());
+    let name_runs = count_runs(move || {
+        store.name().get();
+    });
+    let age_runs = count_runs(move || {
+        store.age().get();
+    });
+    let city_runs = count_runs(move || {
+        store.address().city().get();
+    });
+    let street_runs = count_runs(move || {
+        store.address().street().get();
+    });
+
+    let mut moved = user();
+    moved.address.city = "Bergen".to_string();
+    store.set(moved);
+    store.set(user());
+
+    assert_eq!(city_runs.get(), 3);
+    assert_eq!(name_runs.get(), 1);
+    assert_eq!(age_runs.get(), 1);
+    assert_eq!(street_runs.get(), 1);
+}
+
+// Fields that cannot be compared only rule out `set`.
+#[derive(Clone, Store)]
+struct Callbacks {
+    on_save: Rc<dyn Fn() -> u32>,
+}
+
+#[test]
+fn fields_do_not_need_to_be_comparable() {
+    let callbacks = create_store(Callbacks {
+        on_save: Rc::new(|| 1),
+    });
+    callbacks.on_save().set(Rc::new(|| 2));
+    assert_eq!((callbacks.get().on_save)(), 2);
+}
+
+// Not `Clone`, so only `get` is ruled out.
+struct Connection {
+    id: u32,
+}
+
+#[derive(Store)]
+struct Session {
+    connection: Connection,
+    visits: u32,
+}
+
+#[test]
+fn fields_do_not_need_to_be_cloneable() {
+    let session = create_store(Session {
+        connection: Connection { id: 1 },
+        visits: 0,
+    });
+    let id_runs = count_runs(move || {
+        session.connection().with(|connection| connection.id);
+    });
+
+    session.connection().update(|connection| connection.id = 2);
+    session.visits().update(|visits| *visits += 1);
+
+    assert_eq!(session.connection().with(|connection| connection.id), 2);
+    assert_eq!(session.visits().get(), 1);
+    assert_eq!(id_runs.get(), 2);
+}
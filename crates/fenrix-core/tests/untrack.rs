@@ -0,0 +1,102 @@
+use fenrix_core::{create_effect, create_signal, untrack, watch};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+#[test]
+fn untracked_reads_do_not_subscribe() {
+    let (tracked, set_tracked) = create_signal(0);
+    let (peeked, set_peeked) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        create_effect(move || {
+            tracked();
+            untrack(&peeked);
+            runs.set(runs.get() + 1);
+        });
+    }
+
+    set_peeked(1);
+    assert_eq!(
+        runs.get(),
+        1,
+        "Untracked signal should not re-run the effect."
+    );
+
+    set_tracked(1);
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn tracking_resumes_after_untrack() {
+    let (a, set_a) = create_signal(0);
+    let (b, set_b) = create_signal(0);
+    let runs = Rc::new(Cell::new(0));
+    {
+        let runs = Rc::clone(&runs);
+        create_effect(move || {
+            untrack(&a);
+            b();
+            runs.set(runs.get() + 1);
+        });
+    }
+
+    set_a(1);
+    assert_eq!(runs.get(), 1);
+    set_b(1);
+    assert_eq!(
+        runs.get(),
+        2,
+        "Reads after `untrack` should be tracked again."
+    );
+}
+
+#[test]
+fn watch_receives_new_and_previous_values() {
+    let (path, set_path) = create_signal("/".to_string());
+    let changes = Rc::new(RefCell::new(Vec::new()));
+    {
+        let changes = Rc::clone(&changes);
+        watch(path, move |new, previous| {
+            changes.borrow_mut().push((new.clone(), previous.clone()));
+        });
+    }
+    assert!(
+        changes.borrow().is_empty(),
+        "Callback should not run for the initial value."
+    );
+
+    set_path("/about".to_string());
+    set_path("/about".to_string());
+    set_path("/".to_string());
+    assert_eq!(
+        *changes.borrow(),
+        vec![
+            ("/about".to_string(), "/".to_string()),
+            ("/".to_string(), "/about".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn watch_callback_does_not_track_its_reads() {
+    let (source, set_source) = create_signal(0);
+    let (other, set_other) = create_signal(0);
+    let calls = Rc::new(Cell::new(0));
+    {
+        let calls = Rc::clone(&calls);
+        watch(source, move |_, _| {
+            other();
+            calls.set(calls.get() + 1);
+        });
+    }
+
+    set_source(1);
+    assert_eq!(calls.get(), 1);
+    set_other(1);
+    assert_eq!(
+        calls.get(),
+        1,
+        "Signals read in the callback should not trigger it."
+    );
+}
@@ -0,0 +1,73 @@
+//! The `<Suspense>` and `<ErrorBoundary>` components.
+//!
+//! Both are rendered by the `rsx!` macro, which passes the `fallback` prop and
+//! a closure rendering the children to the functions below.
+
+use crate::{replace_view, View};
+use fenrix_core::{
+    create_effect, create_memo, untrack, CapturedError, ErrorBoundaryContext, SuspenseContext,
+};
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::Node;
+
+/// Renders `fallback` instead of the children while any resource read inside
+/// them is loading.
+///
+/// The children are rendered once, up front, so that their resources start
+/// loading immediately. They stay alive while the fallback is shown and are
+/// put back as soon as every resource has resolved.
+pub fn suspense(fallback: impl Fn() -> Node + 'static, children: impl FnOnce() -> Node) -> Node {
+    let suspense = SuspenseContext::new();
+    let children = View::from(suspense.run(|| untrack(children)));
+    let pending = create_memo(move || suspense.is_pending());
+
+    let current = Rc::new(RefCell::new(None));
+    create_effect({
+        let current = Rc::clone(&current);
+        move || {
+            // The fallback is rendered outside of the boundary, and is
+            // disposed when the children are shown again.
+            let next = if pending() {
+                View::from(untrack(&fallback))
+            } else {
+                children.clone()
+            };
+            replace_view(&current, next);
+        }
+    });
+
+    let view = current.borrow().clone();
+    view.expect("the effect has rendered a view").into()
+}
+
+/// Renders `fallback` instead of the children once they surface an error with
+/// [`fenrix_core::throw_error`] or [`fenrix_core::Resource::read`].
+///
+/// `fallback` receives the error and the boundary. Calling
+/// [`ErrorBoundaryContext::reset`] clears the error and renders the children
+/// again from scratch, which is why they are passed as an `Fn`.
+pub fn error_boundary(
+    fallback: impl Fn(CapturedError, ErrorBoundaryContext) -> Node + 'static,
+    children: impl Fn() -> Node + 'static,
+) -> Node {
+    let boundary = ErrorBoundaryContext::new();
+
+    let current = Rc::new(RefCell::new(None));
+    create_effect({
+        let current = Rc::clone(&current);
+        move || {
+            // Everything rendered by the previous run, including the old
+            // children, is disposed before this runs again.
+            let next = match boundary.error() {
+                // An error thrown by the fallback goes to an outer boundary.
+                Some(error) => untrack(|| fallback(error, boundary)),
+                None => boundary.run(|| untrack(&children)),
+            };
+            replace_view(&current, View::from(next));
+        }
+    });
+
+    let view = current.borrow().clone();
+    view.expect("the effect has rendered a view").into()
+}
@@ -0,0 +1,126 @@
+//! The `<Show>` and `<Switch>`/`<Match>` components.
+//!
+//! Both render one of several branches. The conditions are tracked in a memo
+//! that only selects the branch, so the DOM is swapped when a different branch
+//! is selected and the branch on screen keeps its nodes and reactive state
+//! when anything else changes.
+
+use crate::{replace_view, View};
+use fenrix_core::{create_effect, create_memo, untrack};
+use std::cell::RefCell;
+use std::rc::Rc;
+use web_sys::Node;
+
+/// A branch of [`switch`]: rendered while `when` is `true` and no earlier
+/// branch matches.
+pub struct Match {
+    when: Box<dyn Fn() -> bool>,
+    render: Box<dyn Fn() -> Node>,
+}
+
+impl Match {
+    pub fn new(when: impl Fn() -> bool + 'static, render: impl Fn() -> Node + 'static) -> Self {
+        Match {
+            when: Box::new(when),
+            render: Box::new(render),
+        }
+    }
+}
+
+/// Renders the children while `when` is `true`, and `fallback` otherwise.
+pub fn show(
+    when: impl Fn() -> bool + 'static,
+    fallback: impl Fn() -> Node + 'static,
+    children: impl Fn() -> Node + 'static,
+) -> Node {
+    switch(vec![Match::new(when, children)], fallback)
+}
+
+/// Renders the first branch whose condition is `true`, or `fallback` when
+/// none is.
+///
+/// Each branch is rendered in its own scope when it is selected, and the
+/// scope is disposed when another branch is selected.
+pub fn switch(branches: Vec<Match>, fallback: impl Fn() -> Node + 'static) -> Node {
+    let branches = Rc::new(branches);
+    let selected = select_branch(Rc::clone(&branches));
+
+    let current = Rc::new(RefCell::new(None));
+    create_effect({
+        let current = Rc::clone(&current);
+        move || {
+            // Everything rendered by the previous run is disposed before this
+            // runs again, which only happens when `selected` changes.
+            let next = match selected() {
+                Some(index) => untrack(&branches[index].render),
+                None => untrack(&fallback),
+            };
+            replace_view(&current, View::from(next));
+        }
+    });
+
+    let view = current.borrow().clone();
+    view.expect("the effect has rendered a view").into()
+}
+
+// Tracks which of `branches` is selected: the first whose condition is
+// `true`, or `None`.
+fn select_branch(branches: Rc<Vec<Match>>) -> impl Fn() -> Option<usize> + Clone {
+    create_memo(move || branches.iter().position(|branch| (branch.when)()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_branch, Match};
+    use fenrix_core::{create_effect, create_root, create_rw_signal};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use web_sys::Node;
+
+    fn branch(when: impl Fn() -> bool + 'static) -> Match {
+        Match::new(when, || -> Node {
+            unreachable!("branches are not rendered")
+        })
+    }
+
+    #[test]
+    fn the_first_matching_branch_is_selected() {
+        create_root(|| {
+            let count = create_rw_signal(0);
+            let selected = select_branch(Rc::new(vec![
+                branch(move || count.get() > 10),
+                branch(move || count.get() > 5),
+                branch(move || count.get() > 0),
+            ]));
+            assert_eq!(selected(), None);
+            count.set(3);
+            assert_eq!(selected(), Some(2));
+            count.set(20);
+            assert_eq!(selected(), Some(0));
+        });
+    }
+
+    #[test]
+    fn the_selection_only_changes_with_the_branch() {
+        create_root(|| {
+            let count = create_rw_signal(0);
+            let selected = select_branch(Rc::new(vec![branch(move || count.get() % 2 == 0)]));
+            let swaps = Rc::new(Cell::new(0));
+            create_effect({
+                let swaps = Rc::clone(&swaps);
+                move || {
+                    selected();
+                    swaps.set(swaps.get() + 1);
+                }
+            });
+            count.set(2);
+            count.set(4);
+            assert_eq!(swaps.get(), 1);
+            count.set(5);
+            count.set(7);
+            assert_eq!(swaps.get(), 2);
+            count.set(8);
+            assert_eq!(swaps.get(), 3);
+        });
+    }
+}
@@ -3,6 +3,18 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use web_sys::{window, Document, Element, Node, Text};
 
+mod boundary;
+mod conditional;
+mod list;
+mod storage;
+mod view;
+
+pub use boundary::{error_boundary, suspense};
+pub use conditional::{show, switch, Match};
+pub use list::{for_each, render_signal_map, render_signal_vec};
+pub use storage::WebStorage;
+pub use view::{Children, Slot, View};
+
 /// Gets the `document` object from the browser.
 fn document() -> Document {
     window()
@@ -59,6 +71,31 @@ pub fn create_reactive_text_node(source: impl FnMut() -> String + 'static) -> Te
     text_node
 }
 
+/// Creates content that is rendered again whenever a signal read by `render`
+/// changes. This is what a `{(...)}` expression in `rsx!` expands to.
+///
+/// The new content takes the place of the old, however many nodes either
+/// has, and everything created by the previous render is disposed.
+pub fn create_reactive_view(render: impl Fn() -> View + 'static) -> View {
+    let current = Rc::new(RefCell::new(None));
+    create_effect({
+        let current = Rc::clone(&current);
+        move || replace_view(&current, render())
+    });
+
+    let view = current.borrow().clone();
+    view.expect("the effect has rendered a view")
+}
+
+// Puts `next` in place of the view currently held in `current`, if that view
+// is attached, and remembers `next` for the following swap.
+fn replace_view(current: &RefCell<Option<View>>, next: View) {
+    if let Some(old) = current.borrow().as_ref() {
+        old.replace_with(&next);
+    }
+    *current.borrow_mut() = Some(next);
+}
+
 /// Renders a root node to the document body.
 pub fn render(root_node: Node) {
     let body = document().body().expect("document should have a body");
@@ -0,0 +1,458 @@
+//! List rendering.
+//!
+//! `SignalVec` and `SignalMap` are rendered by applying their diffs, and any
+//! other list by comparing keys in [`for_each`]. Either way every item is
+//! rendered once, in its own scope, and only the items that changed are
+//! touched afterwards: pushing to a list appends one node, and removing an
+//! item removes its node and disposes its scope.
+
+use fenrix_core::{
+    create_effect, create_scope, untrack, MapDiff, Scope, SignalMap, SignalVec, VecDiff,
+};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::rc::Rc;
+use web_sys::{Element, Node};
+
+use crate::{document, View};
+
+// A rendered item: its content and the scope owning everything it created.
+struct Item {
+    view: View,
+    scope: Scope,
+}
+
+impl Item {
+    fn render(list_scope: &Scope, render: impl FnOnce() -> Node) -> Item {
+        let (view, scope) = list_scope.run(|| create_scope(|| View::from(untrack(render))));
+        Item { view, scope }
+    }
+
+    fn remove(self) {
+        self.view.remove();
+        self.scope.dispose();
+    }
+
+    // Puts `view` in the place of this item, and disposes the item.
+    fn replace_with(self, view: &View) {
+        self.view.replace_with(view);
+        self.scope.dispose();
+    }
+}
+
+// Inserts `item` before `next`, or at the end when there is no next item.
+fn insert_item(container: &Element, item: &Item, next: Option<&Item>) {
+    item.view
+        .insert(container, next.map(|next| next.view.first()));
+}
+
+/// Renders every item of `list` into `container` and keeps the children of
+/// `container` in sync with it for as long as the current scope lives.
+///
+/// `container` should not have any other children.
+pub fn render_signal_vec<T: Clone + 'static>(
+    container: &Element,
+    list: SignalVec<T>,
+    render: impl Fn(&T) -> Node + 'static,
+) {
+    // Items are owned by a scope of their own, whatever scope a diff is
+    // applied from.
+    let ((), list_scope) = create_scope(|| {});
+    let items = Rc::new(RefCell::new(Vec::new()));
+    let render_item = move |value: &T| Item::render(&list_scope, || render(value));
+
+    untrack(|| {
+        list.with(|values| {
+            for value in values {
+                let item = render_item(value);
+                insert_item(container, &item, None);
+                items.borrow_mut().push(item);
+            }
+        })
+    });
+
+    // `items` is only borrowed to update it, never while an item is rendered
+    // or disposed, since that runs user code which may mutate the list.
+    let container = container.clone();
+    list.on_diff(move |diff| match diff {
+        VecDiff::Replace { values } => {
+            let old = std::mem::take(&mut *items.borrow_mut());
+            for item in old {
+                item.remove();
+            }
+            for value in values {
+                let item = render_item(value);
+                insert_item(&container, &item, None);
+                items.borrow_mut().push(item);
+            }
+        }
+        VecDiff::Insert { index, value } => {
+            let item = render_item(value);
+            let mut items = items.borrow_mut();
+            insert_item(&container, &item, items.get(*index));
+            items.insert(*index, item);
+        }
+        VecDiff::Set { index, value } => {
+            let item = render_item(value);
+            let view = item.view.clone();
+            let old = std::mem::replace(&mut items.borrow_mut()[*index], item);
+            old.replace_with(&view);
+        }
+        VecDiff::Remove { index } => {
+            let item = items.borrow_mut().remove(*index);
+            item.remove();
+        }
+        VecDiff::Move { from, to } => {
+            let mut items = items.borrow_mut();
+            let item = items.remove(*from);
+            insert_item(&container, &item, items.get(*to));
+            items.insert(*to, item);
+        }
+        VecDiff::Push { value } => {
+            let item = render_item(value);
+            insert_item(&container, &item, None);
+            items.borrow_mut().push(item);
+        }
+        VecDiff::Pop => {
+            let item = items.borrow_mut().pop();
+            if let Some(item) = item {
+                item.remove();
+            }
+        }
+        VecDiff::Clear => {
+            let old = std::mem::take(&mut *items.borrow_mut());
+            for item in old {
+                item.remove();
+            }
+        }
+    });
+}
+
+/// Renders every entry of `map` into `container`, in key order, and keeps
+/// the children of `container` in sync with it for as long as the current
+/// scope lives.
+///
+/// `container` should not have any other children.
+pub fn render_signal_map<K, V>(
+    container: &Element,
+    map: SignalMap<K, V>,
+    render: impl Fn(&K, &V) -> Node + 'static,
+) where
+    K: Ord + Clone + 'static,
+    V: Clone + 'static,
+{
+    let ((), list_scope) = create_scope(|| {});
+    let items = Rc::new(RefCell::new(BTreeMap::new()));
+    let render_item = move |key: &K, value: &V| Item::render(&list_scope, || render(key, value));
+
+    untrack(|| {
+        map.with(|entries| {
+            for (key, value) in entries {
+                let item = render_item(key, value);
+                insert_item(container, &item, None);
+                items.borrow_mut().insert(key.clone(), item);
+            }
+        })
+    });
+
+    let container = container.clone();
+    map.on_diff(move |diff| match diff {
+        MapDiff::Replace { entries } => {
+            let old = std::mem::take(&mut *items.borrow_mut());
+            for (_, item) in old {
+                item.remove();
+            }
+            for (key, value) in entries {
+                let item = render_item(key, value);
+                insert_item(&container, &item, None);
+                items.borrow_mut().insert(key.clone(), item);
+            }
+        }
+        MapDiff::Insert { key, value } => {
+            let item = render_item(key, value);
+            let mut items = items.borrow_mut();
+            // The node goes before the node of the next key in order.
+            let next = items.range(key..).next().map(|(_, next)| next);
+            insert_item(&container, &item, next);
+            items.insert(key.clone(), item);
+        }
+        MapDiff::Update { key, value } => {
+            let item = render_item(key, value);
+            let view = item.view.clone();
+            let old = items.borrow_mut().insert(key.clone(), item);
+            if let Some(old) = old {
+                old.replace_with(&view);
+            }
+        }
+        MapDiff::Remove { key } => {
+            let item = items.borrow_mut().remove(key);
+            if let Some(item) = item {
+                item.remove();
+            }
+        }
+        MapDiff::Clear => {
+            let old = std::mem::take(&mut *items.borrow_mut());
+            for (_, item) in old {
+                item.remove();
+            }
+        }
+    });
+}
+
+/// Renders a row for every value returned by `each`, and keeps the rows in
+/// sync with it. This is what `<For each={...} key={...} let:item>` expands
+/// to.
+///
+/// `each` is tracked like an effect. When it changes, rows are matched to the
+/// new values by `key`: rows whose key is gone are removed and their scopes
+/// disposed, new keys get a new row, and rows that stay are kept as they are,
+/// along with their focus, input state and scroll position. Only rows that
+/// moved relative to the others are moved, so the DOM sees the fewest
+/// insertions the reordering allows. A row is not rendered again when its
+/// value changes but its key does not, so keys should identify the value,
+/// and should be unique.
+///
+/// The rows are returned in a fragment, followed by an empty comment that
+/// marks where the list ends. The fragment must be added to an element.
+pub fn for_each<T, I, K>(
+    each: impl Fn() -> I + 'static,
+    key: impl Fn(&T) -> K + 'static,
+    render: impl Fn(T) -> Node + 'static,
+) -> Node
+where
+    T: 'static,
+    I: IntoIterator<Item = T>,
+    K: Eq + Hash + 'static,
+{
+    let fragment = document().create_document_fragment();
+    let end: Node = document().create_comment("").into();
+    fragment
+        .append_child(&end)
+        .expect("failed to append list marker");
+
+    let ((), list_scope) = create_scope(|| {});
+    let rows = RefCell::new((Vec::<K>::new(), Vec::<Item>::new()));
+    create_effect(move || {
+        let values: Vec<T> = each().into_iter().collect();
+        untrack(|| {
+            let (old_keys, old_items) = std::mem::take(&mut *rows.borrow_mut());
+            let new = reconcile(old_keys, old_items, values, &key, &end, |value| {
+                Item::render(&list_scope, || render(value))
+            });
+            *rows.borrow_mut() = new;
+        });
+    });
+
+    fragment.into()
+}
+
+// Turns the rows of `old_keys` into rows for `values`, reusing rows by key,
+// and updates the DOM before `end` to match.
+fn reconcile<T, K: Eq + Hash>(
+    old_keys: Vec<K>,
+    old_items: Vec<Item>,
+    values: Vec<T>,
+    key: impl Fn(&T) -> K,
+    end: &Node,
+    render: impl Fn(T) -> Item,
+) -> (Vec<K>, Vec<Item>) {
+    let keys: Vec<K> = values.iter().map(key).collect();
+    let plan = plan(&old_keys, &keys);
+
+    let mut old: Vec<Option<Item>> = old_items.into_iter().map(Some).collect();
+    for &index in &plan.removed {
+        if let Some(item) = old[index].take() {
+            item.remove();
+        }
+    }
+    let items: Vec<Item> = values
+        .into_iter()
+        .zip(&plan.reused)
+        .map(|(value, reused)| match reused {
+            Some(index) => old[*index].take().expect("each row is reused once"),
+            None => render(value),
+        })
+        .collect();
+
+    let parent = end.parent_node().expect("the list marker has a parent");
+    let mut next = end.clone();
+    for (item, inserted) in items.iter().zip(&plan.inserted).rev() {
+        if *inserted {
+            item.view.insert(&parent, Some(&next));
+        }
+        next = item.view.first().clone();
+    }
+    (keys, items)
+}
+
+// How the rows of a list change when its keys go from `old` to `new`.
+#[derive(Debug, PartialEq)]
+struct Plan {
+    // For every new key, the old row it keeps, if any.
+    reused: Vec<Option<usize>>,
+    // The old rows that are not kept, in order.
+    removed: Vec<usize>,
+    // For every new key, whether its row has to be inserted, because it is
+    // new or because it moved.
+    inserted: Vec<bool>,
+}
+
+// Matches `new` keys to `old` rows. A key that appears several times takes
+// the old rows with that key in order.
+fn plan<K: Eq + Hash>(old: &[K], new: &[K]) -> Plan {
+    let mut old_index: HashMap<&K, Vec<usize>> = HashMap::new();
+    for (index, key) in old.iter().enumerate().rev() {
+        old_index.entry(key).or_default().push(index);
+    }
+    let reused: Vec<Option<usize>> = new
+        .iter()
+        .map(|key| old_index.get_mut(key).and_then(Vec::pop))
+        .collect();
+
+    let mut is_reused = vec![false; old.len()];
+    for index in reused.iter().flatten() {
+        is_reused[*index] = true;
+    }
+    let removed = (0..old.len()).filter(|index| !is_reused[*index]).collect();
+
+    // Rows whose old positions increase along a longest run stay where they
+    // are; everything else is inserted in front of its successor.
+    let inserted = longest_increasing_run(&reused)
+        .into_iter()
+        .map(|kept| !kept)
+        .collect();
+    Plan {
+        reused,
+        removed,
+        inserted,
+    }
+}
+
+// Marks the entries of a longest strictly increasing subsequence of the
+// `Some` values in `positions`.
+fn longest_increasing_run(positions: &[Option<usize>]) -> Vec<bool> {
+    // `tails[len]` is the entry ending the best run of length `len + 1`.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; positions.len()];
+    for (entry, position) in positions.iter().enumerate() {
+        let Some(position) = *position else {
+            continue;
+        };
+        let len = tails.partition_point(|&tail| positions[tail] < Some(position));
+        if len > 0 {
+            previous[entry] = Some(tails[len - 1]);
+        }
+        if len == tails.len() {
+            tails.push(entry);
+        } else {
+            tails[len] = entry;
+        }
+    }
+
+    let mut kept = vec![false; positions.len()];
+    let mut entry = tails.last().copied();
+    while let Some(current) = entry {
+        kept[current] = true;
+        entry = previous[current];
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan, Plan};
+
+    #[test]
+    fn new_keys_are_inserted() {
+        assert_eq!(
+            plan(&["a", "c"], &["a", "b", "c", "d"]),
+            Plan {
+                reused: vec![Some(0), None, Some(1), None],
+                removed: vec![],
+                inserted: vec![false, true, false, true],
+            }
+        );
+    }
+
+    #[test]
+    fn missing_keys_are_removed() {
+        assert_eq!(
+            plan(&["a", "b", "c"], &["c"]),
+            Plan {
+                reused: vec![Some(2)],
+                removed: vec![0, 1],
+                inserted: vec![false],
+            }
+        );
+    }
+
+    #[test]
+    fn only_moved_rows_are_inserted() {
+        // Moving the last row to the front leaves the others in place.
+        assert_eq!(
+            plan(&["a", "b", "c", "d"], &["d", "a", "b", "c"]),
+            Plan {
+                reused: vec![Some(3), Some(0), Some(1), Some(2)],
+                removed: vec![],
+                inserted: vec![true, false, false, false],
+            }
+        );
+        // Swapping two rows moves one of them.
+        assert_eq!(
+            plan(&["a", "b", "c", "d"], &["a", "c", "b", "d"]).inserted,
+            vec![false, true, false, false]
+        );
+        // Reversing keeps a single row in place.
+        let reversed = plan(&[1, 2, 3], &[3, 2, 1]);
+        assert_eq!(reversed.reused, vec![Some(2), Some(1), Some(0)]);
+        assert_eq!(
+            reversed
+                .inserted
+                .iter()
+                .filter(|inserted| !**inserted)
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn duplicate_keys_reuse_rows_in_order() {
+        assert_eq!(
+            plan(&["a", "b", "a"], &["a", "a", "a"]),
+            Plan {
+                reused: vec![Some(0), Some(2), None],
+                removed: vec![1],
+                inserted: vec![false, false, true],
+            }
+        );
+    }
+
+    #[test]
+    fn empty_lists() {
+        assert_eq!(
+            plan::<&str>(&[], &[]),
+            Plan {
+                reused: vec![],
+                removed: vec![],
+                inserted: vec![],
+            }
+        );
+        assert_eq!(
+            plan(&[], &["a", "b"]),
+            Plan {
+                reused: vec![None, None],
+                removed: vec![],
+                inserted: vec![true, true],
+            }
+        );
+        assert_eq!(
+            plan(&["a", "b"], &[]),
+            Plan {
+                reused: vec![],
+                removed: vec![0, 1],
+                inserted: vec![],
+            }
+        );
+    }
+}
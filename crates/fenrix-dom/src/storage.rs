@@ -0,0 +1,89 @@
+//! `localStorage` and `sessionStorage` as a `fenrix_core::Storage`.
+
+use fenrix_core::{Storage, StorageListener};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{window, StorageEvent};
+
+/// The browser's `localStorage` or `sessionStorage`, for persisted signals.
+///
+/// ```ignore
+/// provide_service_as::<dyn Storage>(Rc::new(WebStorage::local()));
+/// let theme = create_persisted_signal("theme", Theme::Light);
+/// ```
+pub struct WebStorage {
+    storage: web_sys::Storage,
+}
+
+impl WebStorage {
+    /// The `localStorage` of the current origin, shared by all of its tabs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the browser denies access to it.
+    pub fn local() -> Self {
+        let storage = window()
+            .expect("should have a window")
+            .local_storage()
+            .ok()
+            .flatten()
+            .expect("localStorage should be available");
+        WebStorage { storage }
+    }
+
+    /// The `sessionStorage` of the current tab.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the browser denies access to it.
+    pub fn session() -> Self {
+        let storage = window()
+            .expect("should have a window")
+            .session_storage()
+            .ok()
+            .flatten()
+            .expect("sessionStorage should be available");
+        WebStorage { storage }
+    }
+}
+
+impl Storage for WebStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        self.storage.get_item(key).ok().flatten()
+    }
+
+    // Writes can fail when the storage is full; the value is then only kept
+    // in memory.
+    fn set(&self, key: &str, value: &str) {
+        let _ = self.storage.set_item(key, value);
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = self.storage.remove_item(key);
+    }
+
+    // The browser fires `storage` events in every other tab of the origin.
+    // Clearing the whole storage has no key and is not reported.
+    fn subscribe(&self, listener: StorageListener) -> Box<dyn FnOnce()> {
+        let storage = JsValue::from(self.storage.clone());
+        let on_storage = Closure::wrap(Box::new(move |event: StorageEvent| {
+            let is_this_storage = event
+                .storage_area()
+                .is_some_and(|area| JsValue::from(area) == storage);
+            if let (true, Some(key)) = (is_this_storage, event.key()) {
+                listener(&key, event.new_value().as_deref());
+            }
+        }) as Box<dyn FnMut(_)>);
+
+        let window = window().expect("should have a window");
+        window
+            .add_event_listener_with_callback("storage", on_storage.as_ref().unchecked_ref())
+            .expect("failed to add storage listener");
+        Box::new(move || {
+            let _ = window.remove_event_listener_with_callback(
+                "storage",
+                on_storage.as_ref().unchecked_ref(),
+            );
+        })
+    }
+}
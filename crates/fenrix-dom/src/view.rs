@@ -0,0 +1,236 @@
+//! Content made of any number of sibling nodes.
+
+use crate::document;
+use std::rc::Rc;
+use wasm_bindgen::JsCast;
+use web_sys::{DocumentFragment, Element, Node, Text};
+
+/// The children of a component, as a closure that renders them.
+///
+/// A component takes them with a `children: Children` parameter, and `rsx!`
+/// passes whatever is between `<Card>` and `</Card>` to it.
+pub type Children = Rc<dyn Fn() -> Node>;
+
+/// The content of a named slot of a component, such as the header of a card.
+///
+/// A component declares a slot as a `Slot` parameter, usually marked
+/// `#[prop(optional)]`, and `rsx!` passes the content of `<slot:header>` to
+/// the `header` parameter. A slot that was not given renders nothing, or the
+/// fallback passed to [`Slot::render_or`].
+#[derive(Clone, Default)]
+pub struct Slot(Option<Children>);
+
+impl Slot {
+    pub fn new(render: impl Fn() -> Node + 'static) -> Self {
+        Slot(Some(Rc::new(render)))
+    }
+
+    /// Returns whether content was given for the slot.
+    pub fn is_provided(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Renders the content of the slot, or nothing if it was not given.
+    pub fn render(&self) -> Node {
+        self.render_or(|| View::empty().into())
+    }
+
+    /// Renders the content of the slot, or `fallback` if it was not given.
+    pub fn render_or(&self, fallback: impl FnOnce() -> Node) -> Node {
+        match &self.0 {
+            Some(render) => render(),
+            None => fallback(),
+        }
+    }
+}
+
+impl From<Children> for Slot {
+    fn from(render: Children) -> Self {
+        Slot(Some(render))
+    }
+}
+
+/// Content rendered by a template or a component: zero, one or many sibling
+/// nodes.
+///
+/// A `DocumentFragment` is emptied when it is added to an element, so a view
+/// of several nodes keeps them between two empty comments instead. The
+/// comments mark where the content starts and ends wherever it is added,
+/// which lets it be moved, removed and replaced as a whole later on.
+///
+/// Every `Node` converts into a view, and a view converts back into a single
+/// `Node` that adds all of its content when appended.
+#[derive(Clone, Debug)]
+pub struct View(Content);
+
+#[derive(Clone, Debug)]
+enum Content {
+    Node(Node),
+    Range { start: Node, end: Node },
+}
+
+impl View {
+    /// Returns a view with no content.
+    pub fn empty() -> Self {
+        View::fragment([])
+    }
+
+    /// Returns a view of `nodes`, as siblings in the given order.
+    pub fn fragment(nodes: impl IntoIterator<Item = Node>) -> Self {
+        let fragment = document().create_document_fragment();
+        for node in nodes {
+            fragment
+                .append_child(&node)
+                .expect("failed to append to fragment");
+        }
+        View::from(Node::from(fragment))
+    }
+
+    /// Returns the first node of the view, which the content of a view
+    /// inserted before it goes in front of.
+    pub(crate) fn first(&self) -> &Node {
+        match &self.0 {
+            Content::Node(node) => node,
+            Content::Range { start, .. } => start,
+        }
+    }
+
+    /// Inserts the view into `parent` before `next`, or at the end, moving it
+    /// from wherever it was.
+    pub(crate) fn insert(&self, parent: &Node, next: Option<&Node>) {
+        parent
+            .insert_before(&self.to_node(), next)
+            .expect("failed to insert view");
+    }
+
+    /// Removes the view from its parent. The nodes of a range stay together,
+    /// so the view can be inserted again.
+    pub(crate) fn remove(&self) {
+        match &self.0 {
+            Content::Node(node) => {
+                if let Some(parent) = node.parent_node() {
+                    parent.remove_child(node).expect("failed to remove view");
+                }
+            }
+            Content::Range { .. } => {
+                self.to_node();
+            }
+        }
+    }
+
+    /// Puts `next` in the place of this view, if this view has a parent.
+    pub(crate) fn replace_with(&self, next: &View) {
+        if self.first().is_same_node(Some(next.first())) {
+            return;
+        }
+        if let Some(parent) = self.first().parent_node() {
+            next.insert(&parent, Some(self.first()));
+            self.remove();
+        }
+    }
+
+    // Returns a node that adds the whole view when inserted: the node itself,
+    // or a new fragment that the nodes of the range are moved into.
+    fn to_node(&self) -> Node {
+        match &self.0 {
+            Content::Node(node) => node.clone(),
+            Content::Range { start, end } => {
+                let fragment = document().create_document_fragment();
+                let mut next = Some(start.clone());
+                while let Some(node) = next {
+                    next = if node.is_same_node(Some(end)) {
+                        None
+                    } else {
+                        node.next_sibling()
+                    };
+                    fragment
+                        .append_child(&node)
+                        .expect("failed to append to fragment");
+                }
+                fragment.into()
+            }
+        }
+    }
+}
+
+impl From<Node> for View {
+    /// A `DocumentFragment` becomes a view of its children. Any other node is
+    /// a view of itself.
+    fn from(node: Node) -> Self {
+        match node.dyn_into::<DocumentFragment>() {
+            Ok(fragment) => {
+                let start: Node = document().create_comment("").into();
+                let end: Node = document().create_comment("").into();
+                fragment
+                    .insert_before(&start, fragment.first_child().as_ref())
+                    .expect("failed to insert view marker");
+                fragment
+                    .append_child(&end)
+                    .expect("failed to append view marker");
+                View(Content::Range { start, end })
+            }
+            Err(node) => View(Content::Node(node)),
+        }
+    }
+}
+
+impl From<Element> for View {
+    fn from(element: Element) -> Self {
+        View(Content::Node(element.into()))
+    }
+}
+
+impl From<Text> for View {
+    fn from(text: Text) -> Self {
+        View(Content::Node(text.into()))
+    }
+}
+
+impl From<View> for Node {
+    fn from(view: View) -> Self {
+        view.to_node()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Children, Slot};
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::Node;
+
+    // A stand-in for rendered content, which needs no DOM to exist.
+    fn node() -> Node {
+        JsValue::NULL.unchecked_into()
+    }
+
+    #[test]
+    fn a_missing_slot_renders_the_fallback() {
+        let slot = Slot::default();
+        assert!(!slot.is_provided());
+        let fallback = Cell::new(false);
+        slot.render_or(|| {
+            fallback.set(true);
+            node()
+        });
+        assert!(fallback.get());
+    }
+
+    #[test]
+    fn a_provided_slot_renders_its_content() {
+        let rendered = Rc::new(Cell::new(0));
+        let children: Children = Rc::new({
+            let rendered = Rc::clone(&rendered);
+            move || {
+                rendered.set(rendered.get() + 1);
+                node()
+            }
+        });
+        let slot = Slot::from(children);
+        assert!(slot.is_provided());
+        slot.render_or(|| unreachable!("the slot was provided"));
+        slot.clone().render();
+        assert_eq!(rendered.get(), 2);
+    }
+}
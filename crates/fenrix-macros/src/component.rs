@@ -0,0 +1,334 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Expr, FnArg, Ident, ItemFn, Pat, PatIdent, Type};
+
+pub fn component(item: TokenStream) -> TokenStream {
+    let func = parse_macro_input!(item as ItemFn);
+    match expand(func) {
+        Ok(expanded) => expanded.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+// How a prop gets its value when it is not set.
+enum Fallback {
+    Required,
+    Default,
+    Value(Expr),
+}
+
+// A parameter of the component, which becomes a field of its props.
+struct Prop {
+    attrs: Vec<Attribute>,
+    pat: Pat,
+    name: Ident,
+    ty: Type,
+    fallback: Fallback,
+    into: bool,
+}
+
+impl Prop {
+    fn parse(arg: FnArg) -> syn::Result<Prop> {
+        let FnArg::Typed(arg) = arg else {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "components cannot take `self`",
+            ));
+        };
+        let Pat::Ident(pat @ PatIdent { subpat: None, .. }) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(
+                &arg.pat,
+                "component parameters must be plain names, as in `title: String`",
+            ));
+        };
+
+        let mut prop = Prop {
+            attrs: Vec::new(),
+            pat: (*arg.pat).clone(),
+            name: pat.ident.clone(),
+            ty: (*arg.ty).clone(),
+            fallback: Fallback::Required,
+            into: false,
+        };
+        for attr in arg.attrs {
+            if !attr.path().is_ident("prop") {
+                prop.attrs.push(attr);
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("optional") {
+                    prop.fallback = Fallback::Default;
+                } else if meta.path.is_ident("default") {
+                    prop.fallback = Fallback::Value(meta.value()?.parse()?);
+                } else if meta.path.is_ident("into") {
+                    prop.into = true;
+                } else {
+                    return Err(meta.error("expected `optional`, `default = ...` or `into`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(prop)
+    }
+
+    fn is_required(&self) -> bool {
+        matches!(self.fallback, Fallback::Required)
+    }
+
+    // Slots are filled with `<slot:name>` rather than set as attributes.
+    fn is_slot(&self) -> bool {
+        match &self.ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Slot"),
+            _ => false,
+        }
+    }
+
+    // The parameter type of the setter, and how its argument becomes the value.
+    fn setter_input(&self) -> (TokenStream2, TokenStream2) {
+        let (name, ty) = (&self.name, &self.ty);
+        if self.into {
+            (
+                quote! { impl ::std::convert::Into<#ty> },
+                quote! { ::std::convert::Into::into(#name) },
+            )
+        } else {
+            (quote! { #ty }, quote! { #name })
+        }
+    }
+}
+
+fn expand(mut func: ItemFn) -> syn::Result<TokenStream2> {
+    let original_block = func.block;
+    // The name shows up in hook order errors.
+    let name = func.sig.ident.to_string();
+
+    if func.sig.inputs.is_empty() {
+        func.block = syn::parse2(quote! {
+            {
+                fenrix_core::with_named_component_context(#name, || #original_block)
+            }
+        })?;
+        return Ok(quote! { #func });
+    }
+
+    if !func.sig.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &func.sig.generics,
+            "components with props cannot be generic",
+        ));
+    }
+
+    let props = std::mem::take(&mut func.sig.inputs)
+        .into_iter()
+        .map(Prop::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let vis = &func.vis;
+    let props_name = format_ident!("{}Props", func.sig.ident);
+    let builder_name = format_ident!("{}PropsBuilder", func.sig.ident);
+    let names: Vec<_> = props.iter().map(|prop| &prop.name).collect();
+
+    // Each required prop has a type parameter on the builder, which is `()`
+    // until the prop is set and `(T,)` after. `build` needs every one of them
+    // to be set.
+    let required: Vec<_> = props.iter().filter(|prop| prop.is_required()).collect();
+    let states: Vec<_> = required
+        .iter()
+        .map(|prop| format_ident!("__{}", camel_case(&prop.name)))
+        .collect();
+    let traits: Vec<_> = required
+        .iter()
+        .map(|prop| format_ident!("__{}{}", props_name, camel_case(&prop.name)))
+        .collect();
+
+    let builder_fields = props.iter().map(|prop| {
+        let (name, ty) = (&prop.name, &prop.ty);
+        match required.iter().position(|required| required.name == *name) {
+            Some(index) => {
+                let state = &states[index];
+                quote! { #name: #state }
+            }
+            None => quote! { #name: ::std::option::Option<#ty> },
+        }
+    });
+    let unset_fields = props.iter().map(|prop| {
+        let name = &prop.name;
+        if prop.is_required() {
+            quote! { #name: () }
+        } else {
+            quote! { #name: ::std::option::Option::None }
+        }
+    });
+    let unset_states: Vec<_> = required.iter().map(|_| quote! { () }).collect();
+
+    let required_setters = required.iter().enumerate().map(|(index, prop)| {
+        let name = &prop.name;
+        let ty = &prop.ty;
+        let (input, value) = prop.setter_input();
+        let other_states = states
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != index)
+            .map(|(_, state)| state);
+        let before = states.iter().enumerate().map(|(other, state)| {
+            if other == index {
+                quote! { () }
+            } else {
+                quote! { #state }
+            }
+        });
+        let after = states.iter().enumerate().map(|(other, state)| {
+            if other == index {
+                quote! { (#ty,) }
+            } else {
+                quote! { #state }
+            }
+        });
+        let others: Vec<_> = names.iter().filter(|other| **other != name).collect();
+        quote! {
+            impl<#(#other_states),*> #builder_name<#(#before),*> {
+                #vis fn #name(self, #name: #input) -> #builder_name<#(#after),*> {
+                    #builder_name {
+                        #name: (#value,),
+                        #(#others: self.#others,)*
+                    }
+                }
+            }
+        }
+    });
+
+    let optional_setters = props.iter().filter(|prop| !prop.is_required()).map(|prop| {
+        let name = &prop.name;
+        let (input, value) = prop.setter_input();
+        quote! {
+            #vis fn #name(mut self, #name: #input) -> Self {
+                self.#name = ::std::option::Option::Some(#value);
+                self
+            }
+        }
+    });
+
+    let built_fields = props.iter().map(|prop| {
+        let name = &prop.name;
+        match &prop.fallback {
+            Fallback::Required => {
+                let index = required
+                    .iter()
+                    .position(|required| required.name == *name)
+                    .expect("required props have a state");
+                let required_trait = &traits[index];
+                quote! { #name: #required_trait::value(self.#name) }
+            }
+            Fallback::Default => quote! { #name: self.#name.unwrap_or_default() },
+            Fallback::Value(default) => quote! { #name: self.#name.unwrap_or_else(|| #default) },
+        }
+    });
+
+    let required_traits = required.iter().zip(&traits).map(|(prop, required_trait)| {
+        let message = format!("missing required prop `{}` for `<{}>`", prop.name, name);
+        let label = if prop.is_slot() {
+            format!("add a `<slot:{}>` child to `<{}>`", prop.name, name)
+        } else {
+            format!("add `{}={{...}}` to `<{}>`", prop.name, name)
+        };
+        quote! {
+            #[doc(hidden)]
+            #[diagnostic::on_unimplemented(message = #message, label = #label)]
+            #vis trait #required_trait<T> {
+                fn value(self) -> T;
+            }
+
+            impl<T> #required_trait<T> for (T,) {
+                fn value(self) -> T {
+                    self.0
+                }
+            }
+        }
+    });
+    let build_bounds =
+        states
+            .iter()
+            .zip(&traits)
+            .zip(&required)
+            .map(|((state, required_trait), prop)| {
+                let ty = &prop.ty;
+                quote! { #state: #required_trait<#ty> }
+            });
+
+    let field_attrs = props.iter().map(|prop| &prop.attrs);
+    let types = props.iter().map(|prop| &prop.ty);
+    let pats = props.iter().map(|prop| &prop.pat);
+    let props_doc = format!("The props of [`{}`].", name);
+
+    func.sig.inputs = syn::parse_quote! { props: #props_name };
+    func.block = syn::parse2(quote! {
+        {
+            let #props_name { #(#pats,)* } = props;
+            fenrix_core::with_named_component_context(#name, || #original_block)
+        }
+    })?;
+
+    Ok(quote! {
+        #[doc = #props_doc]
+        #vis struct #props_name {
+            #(#(#field_attrs)* #vis #names: #types,)*
+        }
+
+        impl #props_name {
+            /// Returns a builder for the props.
+            #vis fn builder() -> #builder_name<#(#unset_states),*> {
+                <Self as fenrix_core::Props>::builder()
+            }
+        }
+
+        impl fenrix_core::Props for #props_name {
+            type Builder = #builder_name<#(#unset_states),*>;
+
+            fn builder() -> Self::Builder {
+                #builder_name { #(#unset_fields,)* }
+            }
+        }
+
+        #[doc(hidden)]
+        #vis struct #builder_name<#(#states),*> {
+            #(#builder_fields,)*
+        }
+
+        #(#required_traits)*
+
+        #(#required_setters)*
+
+        impl<#(#states),*> #builder_name<#(#states),*> {
+            #(#optional_setters)*
+
+            #vis fn build(self) -> #props_name
+            where
+                #(#build_bounds,)*
+            {
+                #props_name { #(#built_fields,)* }
+            }
+        }
+
+        #func
+    })
+}
+
+// `font_size` becomes `FontSize`.
+fn camel_case(name: &Ident) -> String {
+    name.to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<String>>()
+        .concat()
+}
@@ -0,0 +1,104 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{parse_macro_input, Fields, GenericArgument, ItemStruct, PathArguments, Token, Type};
+
+pub fn injectable(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemStruct);
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "`#[injectable]` does not support generic structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let name = &input.ident;
+    let mut dependencies = Vec::new();
+    let mut values = Vec::new();
+    for field in &input.fields {
+        // `Rc<Dep>` fields are injected; anything else starts out as its
+        // default value.
+        match rc_target(&field.ty) {
+            Some(dependency) => {
+                dependencies.push(quote! { fenrix_core::Dependency::of::<#dependency>() });
+                values.push(quote! { fenrix_core::inject::<#dependency>() });
+            }
+            None => values.push(quote! { ::std::default::Default::default() }),
+        }
+    }
+
+    let construct = match &input.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { #name { #(#names: #values,)* } }
+        }
+        Fields::Unnamed(_) => quote! { #name(#(#values,)*) },
+        Fields::Unit => quote! { #name },
+    };
+
+    let expanded = quote! {
+        #input
+
+        impl fenrix_core::Injectable for #name {
+            fn dependencies() -> ::std::vec::Vec<fenrix_core::Dependency> {
+                ::std::vec![#(#dependencies),*]
+            }
+
+            fn construct() -> Self {
+                #construct
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+// Returns `T` if `ty` is `Rc<T>`, however the path to `Rc` is written.
+fn rc_target(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Rc" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(target) if args.args.len() == 1 => Some(target),
+        _ => None,
+    }
+}
+
+pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let services = parse_macro_input!(attr with Punctuated::<Type, Token![,]>::parse_terminated);
+    let input = parse_macro_input!(item as ItemStruct);
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let services = services.iter();
+
+    let expanded = quote! {
+        #input
+
+        impl #name {
+            /// The services of this module. Generated by `#[module]`.
+            #vis fn module() -> fenrix_core::Module {
+                fenrix_core::Module::new()
+                    #(.injectable::<#services>())*
+            }
+
+            /// Provides every service of this module to the global container,
+            /// or reports the first dependency that is not provided.
+            #vis fn provide() -> ::std::result::Result<(), fenrix_core::DiError> {
+                Self::module().provide()
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -1,31 +1,43 @@
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
 use syn::{
     braced, parenthesized,
     ext::IdentExt,
     parse::{Parse, ParseStream},
-    parse_macro_input, token, Expr, Ident, ItemFn, LitStr, Path, Result, Token,
+    parse_macro_input, token, Expr, Ident, LitStr, Path, Result, Token,
 };
 
-/// Represents the overall RSX structure. For now, a single root node.
+/// Represents the overall RSX structure: a single root node, or several
+/// sibling roots, which are rendered as a fragment.
 struct RsxInput {
     root: Node,
 }
 
 impl Parse for RsxInput {
     fn parse(input: ParseStream) -> Result<Self> {
-        Ok(RsxInput {
-            root: input.parse()?,
-        })
+        let mut roots = vec![input.parse()?];
+        while !input.is_empty() {
+            roots.push(input.parse()?);
+        }
+        let root = if roots.len() == 1 {
+            roots.remove(0)
+        } else {
+            Node::Fragment(roots)
+        };
+        Ok(RsxInput { root })
     }
 }
 
 /// Represents a node in the RSX tree.
+#[allow(clippy::enum_variant_names)]
 enum Node {
     Element(Element),
     Component(ComponentElement),
+    Fragment(Vec<Node>),
+    Slot(SlotElement),
     Text(LitStr),
     ReactiveText(Expr),
     RenderedNode(Expr),
@@ -33,7 +45,19 @@ enum Node {
 
 impl Parse for Node {
     fn parse(input: ParseStream) -> Result<Self> {
-        if input.peek(Token![<]) {
+        if input.peek(Token![<]) && input.peek2(Token![>]) {
+            // A fragment: `<>...</>`
+            input.parse::<Token![<]>()?;
+            input.parse::<Token![>]>()?;
+            let mut children = Vec::new();
+            while !input.peek(Token![<]) || !input.peek2(Token![/]) {
+                children.push(input.parse()?);
+            }
+            input.parse::<Token![<]>()?;
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            Ok(Node::Fragment(children))
+        } else if input.peek(Token![<]) {
             let fork = input.fork();
             fork.parse::<Token![<]>()?;
             let path: Path = fork.parse()?;
@@ -47,10 +71,12 @@ impl Parse for Node {
                 .next()
                 .unwrap();
 
-            if first_char.is_ascii_uppercase() {
-                return Ok(Node::Component(input.parse()?));
+            if path.is_ident("slot") && fork.peek(Token![:]) {
+                Ok(Node::Slot(input.parse()?))
+            } else if first_char.is_ascii_uppercase() {
+                Ok(Node::Component(input.parse()?))
             } else {
-                return Ok(Node::Element(input.parse()?));
+                Ok(Node::Element(input.parse()?))
             }
         } else if input.peek(LitStr) {
             Ok(Node::Text(input.parse()?))
@@ -203,10 +229,53 @@ impl Parse for Element {
     }
 }
 
+/// Represents the content of a named slot of a component, like
+/// `<slot:header><h1>"Title"</h1></slot:header>`.
+struct SlotElement {
+    name: Ident,
+    children: Vec<Node>,
+}
+
+impl Parse for SlotElement {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // Parse opening tag: `<slot:name>`
+        input.parse::<Token![<]>()?;
+        input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Token![>]>()?;
+
+        let mut children = Vec::new();
+        while !input.peek(Token![<]) || !input.peek2(Token![/]) {
+            children.push(input.parse()?);
+        }
+
+        // Parse closing tag: `</slot:name>`
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        input.parse::<Ident>()?;
+        input.parse::<Token![:]>()?;
+        let closing_name: Ident = input.parse()?;
+        if closing_name != name {
+            let error_message = format!(
+                "Mismatched closing tag: expected `slot:{}`, found `slot:{}`",
+                name, closing_name
+            );
+            return Err(input.error(error_message));
+        }
+        input.parse::<Token![>]>()?;
+
+        Ok(SlotElement { name, children })
+    }
+}
+
 /// Represents a component element like `<MyComponent to="/about">Click Me</MyComponent>`.
 struct ComponentElement {
     name: Path,
     props: Vec<Attribute>,
+    /// The name given by `let:name`, which the children use for the value
+    /// they are rendered with.
+    binding: Option<Ident>,
     children: Vec<Node>,
 }
 
@@ -216,10 +285,17 @@ impl Parse for ComponentElement {
         input.parse::<Token![<]>()?;
         let name: Path = input.parse()?;
 
-        // Parse props
+        // Parse props, and the `let:name` binding
         let mut props = Vec::new();
+        let mut binding = None;
         while !input.peek(Token![>]) && !input.peek(Token![/]) {
-            props.push(input.parse()?);
+            if input.peek(Token![let]) && input.peek2(Token![:]) {
+                input.parse::<Token![let]>()?;
+                input.parse::<Token![:]>()?;
+                binding = Some(input.parse()?);
+            } else {
+                props.push(input.parse()?);
+            }
         }
 
         // Handle self-closing `/>`
@@ -229,6 +305,7 @@ impl Parse for ComponentElement {
             return Ok(ComponentElement {
                 name,
                 props,
+                binding,
                 children: Vec::new(),
             });
         }
@@ -247,8 +324,8 @@ impl Parse for ComponentElement {
         if closing_name != name {
             let error_message = format!(
                 "Mismatched closing tag: expected `{}`, found `{}`",
-                quote!(#name).to_string(),
-                quote!(#closing_name).to_string()
+                quote!(#name),
+                quote!(#closing_name)
             );
             return Err(input.error(error_message));
         }
@@ -257,6 +334,7 @@ impl Parse for ComponentElement {
         Ok(ComponentElement {
             name,
             props,
+            binding,
             children,
         })
     }
@@ -273,6 +351,14 @@ impl ToTokens for Node {
         match self {
             Node::Element(el) => el.to_tokens(tokens),
             Node::Component(comp) => comp.to_tokens(tokens),
+            Node::Fragment(children) => tokens.extend(render_children(children)),
+            Node::Slot(slot) => {
+                let message = format!(
+                    "<slot:{}> can only be used directly inside a component.",
+                    slot.name
+                );
+                tokens.extend(quote_spanned! { slot.name.span()=> compile_error!(#message) });
+            }
             Node::Text(text) => {
                 tokens.extend(quote! {
                     fenrix_dom::create_text_node(#text).into()
@@ -285,27 +371,11 @@ impl ToTokens for Node {
             }
             Node::RenderedNode(expr) => {
                 tokens.extend(quote! {
-                    {
-                        // The effect runs once immediately, so current_node will hold the real node
-                        // after the effect is created. We can then return it to be appended to the DOM.
-                        let current_node = ::std::rc::Rc::new(::std::cell::RefCell::new(None::<::web_sys::Node>));
-                        let effect_current_node = ::std::rc::Rc::clone(&current_node);
-
-                        fenrix_core::create_effect(move || {
-                            let new_node: ::web_sys::Node = #expr;
-
-                            if let Some(old_node) = effect_current_node.borrow().as_ref() {
-                                if let Some(parent) = old_node.parent_node() {
-                                    parent.replace_child(&new_node, old_node).unwrap();
-                                }
-                            }
-
-                            *effect_current_node.borrow_mut() = Some(new_node);
-                        });
-
-                        let borrowed_node = current_node.borrow();
-                        borrowed_node.as_ref().unwrap().clone()
-                    }
+                    fenrix_dom::create_reactive_view(move || {
+                        let new_node: ::web_sys::Node = #expr;
+                        fenrix_dom::View::from(new_node)
+                    })
+                    .into()
                 });
             }
         }
@@ -343,10 +413,22 @@ impl ToTokens for Element {
             if let AttrName::Event(name) = &attr.name {
                 if let AttrValue::Expr(handler) = &attr.value {
                     let event_name = name.to_string();
+                    // Handlers run inside an implicit batch, so an event that updates
+                    // several signals only re-runs the affected effects once.
                     quote! {
-                        let closure = ::wasm_bindgen::prelude::Closure::wrap(Box::new(#handler) as Box<dyn FnMut(_)>);
+                        #[allow(unused_mut)]
+                        let mut handler = #handler;
+                        let closure = ::wasm_bindgen::prelude::Closure::wrap(Box::new(move |event| {
+                            fenrix_core::batch(|| handler(event))
+                        }) as Box<dyn FnMut(_)>);
                         element.add_event_listener_with_callback(#event_name, closure.as_ref().unchecked_ref()).unwrap();
-                        closure.forget();
+                        {
+                            // The listener is removed when the owning scope is disposed.
+                            let element = element.clone();
+                            fenrix_core::on_cleanup(move || {
+                                element.remove_event_listener_with_callback(#event_name, closure.as_ref().unchecked_ref()).unwrap();
+                            });
+                        }
                     }
                 } else {
                     quote! { compile_error!("Event handler must be a closure in braces."); }
@@ -373,7 +455,12 @@ impl ToTokens for Element {
                                 setter(el.value());
                             }) as Box<dyn FnMut(_)>);
                             element.add_event_listener_with_callback("input", closure.as_ref().unchecked_ref()).unwrap();
-                            closure.forget();
+                            {
+                                let element = element.clone();
+                                fenrix_core::on_cleanup(move || {
+                                    element.remove_event_listener_with_callback("input", closure.as_ref().unchecked_ref()).unwrap();
+                                });
+                            }
                         }
                     } else {
                         quote! { compile_error!("Binding value must be a signal expression."); }
@@ -446,15 +533,318 @@ impl ToTokens for ComponentElement {
                     compile_error!("<Link> component requires a 'to' prop.")
                 });
             }
+        } else if name_str == "Suspense" || name_str == "ErrorBoundary" {
+            // Built-in boundaries: `fallback` is passed as is, and the single
+            // child is rendered inside the boundary by a closure.
+            let fallback = match self.prop("fallback") {
+                Some(AttrValue::Expr(expr)) => expr,
+                _ => {
+                    let message = format!(
+                        "<{}> requires a `fallback={{...}}` prop.",
+                        name_str
+                    );
+                    tokens.extend(quote! { compile_error!(#message) });
+                    return;
+                }
+            };
+            let [child] = self.children.as_slice() else {
+                let message = format!("<{}> expects exactly one child.", name_str);
+                tokens.extend(quote! { compile_error!(#message) });
+                return;
+            };
+
+            let render = if name_str == "Suspense" {
+                quote! { fenrix_dom::suspense }
+            } else {
+                quote! { fenrix_dom::error_boundary }
+            };
+            tokens.extend(quote! {
+                #render(#fallback, move || -> web_sys::Node { #child })
+            });
+        } else if name_str == "For" {
+            // Built-in keyed list: the single child is the row, rendered by a
+            // closure that receives each value under the `let:` name.
+            let (Some(AttrValue::Expr(each)), Some(AttrValue::Expr(key))) =
+                (self.prop("each"), self.prop("key"))
+            else {
+                tokens.extend(quote! {
+                    compile_error!("<For> requires `each={...}` and `key={...}` props.")
+                });
+                return;
+            };
+            let Some(binding) = &self.binding else {
+                tokens.extend(quote! {
+                    compile_error!("<For> requires a `let:name` binding for the row value.")
+                });
+                return;
+            };
+            let [child] = self.children.as_slice() else {
+                tokens.extend(quote! { compile_error!("<For> expects exactly one child.") });
+                return;
+            };
+
+            tokens.extend(quote! {
+                fenrix_dom::for_each(#each, #key, move |#binding| -> web_sys::Node { #child })
+            });
+        } else if name_str == "Show" {
+            // Built-in conditional: the single child is rendered while `when`
+            // returns `true`, and the optional `fallback` otherwise.
+            let Some(AttrValue::Expr(when)) = self.prop("when") else {
+                tokens.extend(quote! { compile_error!("<Show> requires a `when={...}` prop.") });
+                return;
+            };
+            let [child] = self.children.as_slice() else {
+                tokens.extend(quote! { compile_error!("<Show> expects exactly one child.") });
+                return;
+            };
+
+            let fallback = self.fallback_or_empty();
+            tokens.extend(quote! {
+                fenrix_dom::show(#when, #fallback, move || -> web_sys::Node { #child })
+            });
+        } else if name_str == "Switch" {
+            // Built-in multi-way conditional: every child is a `<Match>`, and
+            // the first one whose `when` returns `true` is rendered.
+            let mut branches = Vec::new();
+            for child in &self.children {
+                let Node::Component(branch) = child else {
+                    tokens.extend(quote! {
+                        compile_error!("<Switch> only accepts <Match> children.")
+                    });
+                    return;
+                };
+                if !branch.name.is_ident("Match") {
+                    tokens.extend(quote! {
+                        compile_error!("<Switch> only accepts <Match> children.")
+                    });
+                    return;
+                }
+                let Some(AttrValue::Expr(when)) = branch.prop("when") else {
+                    tokens.extend(quote! {
+                        compile_error!("<Match> requires a `when={...}` prop.")
+                    });
+                    return;
+                };
+                let [child] = branch.children.as_slice() else {
+                    tokens.extend(quote! { compile_error!("<Match> expects exactly one child.") });
+                    return;
+                };
+                branches.push(quote! {
+                    fenrix_dom::Match::new(#when, move || -> web_sys::Node { #child })
+                });
+            }
+
+            let fallback = self.fallback_or_empty();
+            tokens.extend(quote! {
+                fenrix_dom::switch(::std::vec![#(#branches),*], #fallback)
+            });
+        } else if name_str == "Match" {
+            tokens.extend(quote! { compile_error!("<Match> can only be used inside <Switch>.") });
         } else {
-            // TODO: Implement passing props and children to user-defined components.
+            // A user component: its props are set on the builder generated by
+            // `#[component]`, and the children are passed as a render closure.
+            // Missing required props are reported on `build`, so the builder
+            // is located at the component's tag, while still marked as coming
+            // from `rsx!`, for the error to point there.
+            let span = proc_macro2::Span::call_site().located_at(name.span());
+            let component = quote!(#name).to_string().replace(' ', "");
+            // The values are evaluated first, out of reach of the fallbacks
+            // below, so that their methods do not clash with them.
+            let mut values = Vec::new();
+            let mut setters = Vec::new();
+            // Every setter has a fallback, which is only picked when the
+            // builder has no such setter and reports the prop as unknown.
+            let mut fallbacks = Vec::new();
+            let mut seen = Vec::new();
+            for prop in &self.props {
+                let AttrName::Standard(prop_name) = &prop.name else {
+                    tokens.extend(quote! {
+                        compile_error!("Components do not take event handlers or bindings.")
+                    });
+                    return;
+                };
+                let prop_name = prop_name
+                    .get_ident()
+                    .expect("attribute names are single identifiers");
+                let value = match &prop.value {
+                    AttrValue::Literal(lit) => quote! { #lit },
+                    AttrValue::Expr(expr) => quote! { #expr },
+                };
+                if seen.contains(prop_name) {
+                    let message = format!(
+                        "`{}` is set more than once on `<{}>`.",
+                        prop_name, component
+                    );
+                    tokens.extend(quote_spanned! { prop_name.span()=> compile_error!(#message) });
+                    return;
+                }
+                seen.push(prop_name.clone());
+                let local = format_ident!("__prop{}", values.len(), span = value.span());
+                values.push(quote! { let #local = #value; });
+                setters.push(quote_spanned! { prop_name.span()=>
+                    let builder = builder.#prop_name(#local);
+                });
+                fallbacks.push(setter_fallback(
+                    prop_name,
+                    fallbacks.len(),
+                    format!("`<{}>` has no prop `{}`", component, prop_name),
+                    "unknown prop",
+                ));
+            }
+            if self.binding.is_some() {
+                tokens.extend(quote! {
+                    compile_error!("Only <For> takes a `let:` binding.")
+                });
+                return;
+            }
+            // `<slot:name>` children set the slot prop of the same name, and
+            // the others are passed as `children`.
+            let mut children = Vec::new();
+            for child in &self.children {
+                match child {
+                    Node::Slot(slot) => {
+                        let slot_name = &slot.name;
+                        if seen.contains(slot_name) {
+                            let message = format!(
+                                "`{}` is set more than once on `<{}>`.",
+                                slot_name, component
+                            );
+                            tokens.extend(quote_spanned! { slot_name.span()=>
+                                compile_error!(#message)
+                            });
+                            return;
+                        }
+                        seen.push(slot_name.clone());
+                        fallbacks.push(setter_fallback(
+                            slot_name,
+                            fallbacks.len(),
+                            format!("`<{}>` has no slot `{}`", component, slot_name),
+                            "unknown slot",
+                        ));
+                        let content = render_children(&slot.children);
+                        let local = format_ident!("__prop{}", values.len());
+                        values.push(quote! {
+                            let #local = fenrix_dom::Slot::new(move || -> web_sys::Node { #content });
+                        });
+                        setters.push(quote_spanned! { slot_name.span()=>
+                            let builder = builder.#slot_name(#local);
+                        });
+                    }
+                    child => children.push(child),
+                }
+            }
+            if !children.is_empty() {
+                fallbacks.push(setter_fallback(
+                    &format_ident!("children", span = name.span()),
+                    fallbacks.len(),
+                    format!("`<{}>` does not take children", component),
+                    &format!("add a `children: Children` parameter to `{}`", component),
+                ));
+                let children = render_children(children);
+                let local = format_ident!("__prop{}", values.len());
+                values.push(quote! {
+                    let #local = ::std::rc::Rc::new(move || -> web_sys::Node { #children })
+                        as fenrix_dom::Children;
+                });
+                // `From` lets the prop be `Children`, `Option<Children>` or `Slot`.
+                setters.push(quote_spanned! { span=>
+                    let builder = builder.children(::std::convert::From::from(#local));
+                });
+            }
+
+            // Each setter is a statement of its own, spanned at its prop, so
+            // that an unknown prop is reported where it is set.
+            let builder = quote_spanned! { span=>
+                let builder = fenrix_core::component_props(&#name);
+            };
+            let build = quote_spanned! { span=> builder.build() };
+            let props = quote! {{
+                #(#values)*
+                {
+                    #(#fallbacks)*
+                    #builder
+                    #(#setters)*
+                    #build
+                }
+            }};
             tokens.extend(quote! {
-                #name()
+                ::std::convert::Into::into(fenrix_core::Component::call(#name, #props))
             });
         }
     }
 }
 
+/// Returns a trait with a `setter` method for every builder, which a setter
+/// call falls back to when the builder has no such method. Its only use is to
+/// fail with `message`, which names the component and the unknown prop.
+fn setter_fallback(
+    setter: &Ident,
+    index: usize,
+    message: String,
+    label: &str,
+) -> proc_macro2::TokenStream {
+    let unknown = format_ident!("__UnknownProp{}", index);
+    let fallback = format_ident!("__PropFallback{}", index);
+    quote! {
+        #[diagnostic::on_unimplemented(message = #message, label = #label)]
+        trait #unknown {}
+
+        #[allow(dead_code)]
+        trait #fallback: ::std::marker::Sized {
+            fn #setter<V>(self, value: V) -> Self
+            where
+                Self: #unknown;
+        }
+
+        impl<B> #fallback for B {
+            fn #setter<V>(self, _value: V) -> Self
+            where
+                Self: #unknown,
+            {
+                self
+            }
+        }
+    }
+}
+
+/// Renders `children` as a single node: the only child itself, or a fragment
+/// of all of them.
+fn render_children<'a>(children: impl IntoIterator<Item = &'a Node>) -> proc_macro2::TokenStream {
+    match children.into_iter().collect::<Vec<_>>().as_slice() {
+        [child] => quote! { #child },
+        children => quote! {
+            fenrix_dom::View::fragment([
+                #({
+                    let child_node: web_sys::Node = #children;
+                    child_node
+                }),*
+            ])
+            .into()
+        },
+    }
+}
+
+impl ComponentElement {
+    /// Returns the `fallback` prop, or a closure rendering nothing when it is
+    /// not given.
+    fn fallback_or_empty(&self) -> proc_macro2::TokenStream {
+        match self.prop("fallback") {
+            Some(AttrValue::Expr(fallback)) => quote! { #fallback },
+            _ => quote! {
+                || -> web_sys::Node { fenrix_dom::View::empty().into() }
+            },
+        }
+    }
+
+    /// Returns the value of the prop called `name`, if it was given.
+    fn prop(&self, name: &str) -> Option<&AttrValue> {
+        self.props.iter().find_map(|prop| match &prop.name {
+            AttrName::Standard(prop_name) if prop_name.is_ident(name) => Some(&prop.value),
+            _ => None,
+        })
+    }
+}
+
 #[proc_macro]
 pub fn rsx(input: TokenStream) -> TokenStream {
     let parsed_input = parse_macro_input!(input as RsxInput);
@@ -466,25 +856,121 @@ pub fn rsx(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+mod component;
+mod injectable;
 mod server;
+mod store;
 
 #[proc_macro_attribute]
 pub fn server(attr: TokenStream, item: TokenStream) -> TokenStream {
     server::server_macro(attr, item)
 }
 
+/// Derives `fenrix_core::Store`, generating a `<Name>Store` type with one
+/// reactive accessor per field. Mark a field with `#[store]` to make it a
+/// nested store instead of a single signal.
+#[proc_macro_derive(Store, attributes(store))]
+pub fn derive_store(item: TokenStream) -> TokenStream {
+    store::derive_store(item)
+}
+
+/// Implements `fenrix_core::Injectable` for a struct. Fields of type
+/// `Rc<Dep>` are injected when the struct is constructed; other fields start
+/// out as their `Default` value.
+#[proc_macro_attribute]
+pub fn injectable(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    injectable::injectable(item)
+}
+
+/// Turns a unit struct into a module listing `#[injectable]` services, as in
+/// `#[module(ApiClient, AuthService)] struct AppModule;`. It gains `module()`
+/// and `provide()` functions that register the services all at once.
+#[proc_macro_attribute]
+pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
+    injectable::module(attr, item)
+}
+
+/// Turns a function into a component. Its parameters become the fields of a
+/// `<Name>Props` struct, built with a builder that `rsx!` fills in from the
+/// props of `<Name prop={...}>`. A parameter can be marked `#[prop(optional)]`
+/// to default to `Default::default()`, `#[prop(default = ...)]` to default to
+/// the given value, and `#[prop(into)]` to accept anything that converts into
+/// its type. Any other parameter is required.
+///
+/// A `children` parameter receives the content between the tags, and a
+/// `fenrix_dom::Slot` parameter the content of the `<slot:name>` child with
+/// the same name.
+///
+/// ```no_run
+/// # use fenrix_macros::{component, rsx};
+/// #[allow(non_snake_case)]
+/// #[component]
+/// fn Badge(count: u32) -> web_sys::Node {
+///     rsx! { <span>{count}</span> }
+/// }
+///
+/// #[allow(non_snake_case)]
+/// #[component]
+/// fn Logo() -> web_sys::Node {
+///     rsx! { <img src="/logo.svg" /> }
+/// }
+///
+/// #[allow(non_snake_case)]
+/// #[component]
+/// fn Panel(children: fenrix_dom::Children) -> web_sys::Node {
+///     rsx! { <section>{(children())}</section> }
+/// }
+///
+/// let unread = vec![1, 2, 3];
+/// let _: web_sys::Node = rsx! {
+///     <Panel>
+///         <Logo />
+///         <Badge count={unread.iter().count() as u32} />
+///         <Badge count={(unread.len() as u32).max(1)} />
+///     </Panel>
+/// };
+/// ```
+///
+/// Leaving out a required prop is an error at the tag, naming the prop:
+///
+/// ```compile_fail,E0277
+/// # use fenrix_macros::{component, rsx};
+/// # #[allow(non_snake_case)]
+/// # #[component]
+/// # fn Badge(count: u32) -> web_sys::Node {
+/// #     rsx! { <span>{count}</span> }
+/// # }
+/// // error: missing required prop `count` for `<Badge>`
+/// let _: web_sys::Node = rsx! { <Badge /> };
+/// ```
+///
+/// So is a prop the component does not have, at the prop:
+///
+/// ```compile_fail,E0277
+/// # use fenrix_macros::{component, rsx};
+/// # #[allow(non_snake_case)]
+/// # #[component]
+/// # fn Badge(count: u32) -> web_sys::Node {
+/// #     rsx! { <span>{count}</span> }
+/// # }
+/// // error: `<Badge>` has no prop `color`
+/// let _: web_sys::Node = rsx! { <Badge count={3} color="red" /> };
+/// ```
+///
+/// And so are children passed to a component without a `children`
+/// parameter:
+///
+/// ```compile_fail,E0277
+/// # use fenrix_macros::{component, rsx};
+/// # #[allow(non_snake_case)]
+/// # #[component]
+/// # fn Logo() -> web_sys::Node {
+/// #     rsx! { <img src="/logo.svg" /> }
+/// # }
+/// // error: `<Logo>` does not take children
+/// let _: web_sys::Node = rsx! { <Logo><span>"Fenrix"</span></Logo> };
+/// ```
 #[proc_macro_attribute]
 pub fn component(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let mut func = parse_macro_input!(item as ItemFn);
-    let original_block = func.block;
-    let new_block_tokens = quote! {
-        {
-            fenrix_core::with_component_context(|| #original_block)
-        }
-    };
-    func.block = Box::new(syn::parse2(new_block_tokens).unwrap());
-    let expanded = quote! {
-        #func
-    };
-    TokenStream::from(expanded)
-}
\ No newline at end of file
+    component::component(item)
+}
@@ -47,58 +47,68 @@ pub fn server_macro(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
 
         // Client-side (Wasm) implementation: Generate a fetch call.
+        // Failures are returned through the function's error type (which must
+        // implement `From<ServerFnError>`) so they can reach an error boundary.
         #[cfg(target_arch = "wasm32")]
         #func_vis #func_sig {
             // This code runs on the client. It serializes the arguments,
             // sends them to the server, and deserializes the response.
             use wasm_bindgen::JsCast;
+            use fenrix_core::ServerFnError;
 
             // 1. Serialize arguments into a JSON string.
             // The arguments are packed into a tuple for serialization.
             let args_tuple = (#(#arg_names,)*);
-            let body_json = serde_json::to_string(&args_tuple)
-                .expect("Failed to serialize server function arguments.");
+            let body_json = serde_json::to_string(&args_tuple).map_err(|e| {
+                ServerFnError::new(format!("Failed to serialize server function arguments: {}", e))
+            })?;
 
             // 2. Prepare the `fetch` request.
-            // 2. Prepare the `fetch` request.
-            let mut opts = ::web_sys::RequestInit::new();
+            let opts = ::web_sys::RequestInit::new();
             opts.set_method("POST");
             opts.set_mode(::web_sys::RequestMode::Cors);
             let body_js_value = wasm_bindgen::JsValue::from_str(&body_json);
             opts.set_body(&body_js_value);
 
             let request = ::web_sys::Request::new_with_str_and_init(#api_url, &opts)
-                .expect("Failed to create request.");
+                .map_err(|e| ServerFnError::new(format!("Failed to create request: {:?}", e)))?;
 
             request.headers().set("Content-Type", "application/json")
-                .expect("Failed to set Content-Type header.");
+                .map_err(|e| ServerFnError::new(format!("Failed to set Content-Type header: {:?}", e)))?;
 
             // 3. Execute the request and await the response.
-            let window = ::web_sys::window().expect("No window found for fetch.");
+            let window = ::web_sys::window()
+                .ok_or_else(|| ServerFnError::new("No window found for fetch."))?;
             let resp_value = wasm_bindgen_futures::JsFuture::from(window.fetch_with_request(&request))
                 .await
-                .expect("Network request for server function failed.");
+                .map_err(|e| ServerFnError::new(format!("Network request for server function failed: {:?}", e)))?;
 
             let resp: ::web_sys::Response = resp_value
                 .dyn_into()
-                .expect("Could not cast JsValue to Response.");
+                .map_err(|e| ServerFnError::new(format!("Could not cast JsValue to Response: {:?}", e)))?;
 
             // 4. Check for HTTP errors.
             if !resp.ok() {
-                let status = resp.status();
-                let status_text = resp.status_text();
-                panic!("Server function call failed with status: {} {}", status, status_text);
+                return Err(ServerFnError::new(format!(
+                    "Server function call failed with status: {} {}",
+                    resp.status(),
+                    resp.status_text()
+                ))
+                .into());
             }
 
             // 5. Deserialize the response body.
-            let json_promise = resp.json().expect("Failed to get JSON promise from response.");
+            let json_promise = resp
+                .json()
+                .map_err(|e| ServerFnError::new(format!("Failed to get JSON promise from response: {:?}", e)))?;
             let json_value = wasm_bindgen_futures::JsFuture::from(json_promise)
                 .await
-                .expect("Failed to resolve JSON promise from server function.");
+                .map_err(|e| ServerFnError::new(format!("Failed to resolve JSON promise from server function: {:?}", e)))?;
 
             // Use `serde_wasm_bindgen` to deserialize the JsValue directly.
-            serde_wasm_bindgen::from_value(json_value)
-                .expect("Failed to deserialize server function response from JSON.")
+            serde_wasm_bindgen::from_value(json_value).map_err(|e| {
+                ServerFnError::new(format!("Failed to deserialize server function response from JSON: {}", e))
+            })?
         }
     };
 
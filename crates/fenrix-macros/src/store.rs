@@ -0,0 +1,146 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Fields};
+
+pub fn derive_store(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(
+            &input.generics,
+            "`#[derive(Store)]` does not support generic structs",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return syn::Error::new_spanned(
+                    &input.ident,
+                    "`#[derive(Store)]` requires a struct with named fields",
+                )
+                .to_compile_error()
+                .into();
+            }
+        },
+        _ => {
+            return syn::Error::new_spanned(
+                &input.ident,
+                "`#[derive(Store)]` can only be used on structs",
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let store_name = format_ident!("{}Store", name);
+    let store_doc = format!(
+        "A reactive store of [`{}`], with one signal per field. Generated by `#[derive(Store)]`.",
+        name
+    );
+
+    let mut store_fields = Vec::new();
+    let mut accessors = Vec::new();
+    let mut inits = Vec::new();
+    let mut gets = Vec::new();
+    let mut sets = Vec::new();
+    let mut get_bounds = Vec::new();
+    let mut set_bounds = Vec::new();
+
+    for field in fields {
+        let field_vis = &field.vis;
+        let field_name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        // Fields marked `#[store]` become nested stores instead of signals.
+        let is_nested = field.attrs.iter().any(|attr| attr.path().is_ident("store"));
+
+        let store_ty = if is_nested {
+            quote! { <#ty as fenrix_core::Store>::Store }
+        } else {
+            quote! { fenrix_core::RwSignal<#ty> }
+        };
+        let init = if is_nested {
+            quote! { fenrix_core::Store::into_store(self.#field_name) }
+        } else {
+            quote! { fenrix_core::create_rw_signal(self.#field_name) }
+        };
+
+        store_fields.push(quote! { #field_name: #store_ty });
+        accessors.push(quote! {
+            #field_vis fn #field_name(&self) -> #store_ty {
+                self.#field_name
+            }
+        });
+        inits.push(quote! { #field_name: #init });
+        gets.push(quote! { #field_name: self.#field_name.get() });
+        if is_nested {
+            sets.push(quote! { self.#field_name.set(value.#field_name); });
+        } else {
+            // `for<'a>` makes the bounds checked where `get` and `set` are
+            // called, so fields that cannot be cloned or compared only rule
+            // out those methods.
+            get_bounds.push(quote! { for<'a> #ty: ::std::clone::Clone });
+            set_bounds.push(quote! { for<'a> #ty: ::std::cmp::PartialEq });
+            // Unchanged fields are not written, so their readers do not re-run.
+            sets.push(quote! {
+                let is_changed = fenrix_core::untrack(|| {
+                    self.#field_name
+                        .with(|current| ::std::cmp::PartialEq::ne(current, &value.#field_name))
+                });
+                if is_changed {
+                    self.#field_name.set(value.#field_name);
+                }
+            });
+        }
+    }
+
+    let expanded = quote! {
+        #[doc = #store_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #store_name {
+            #(#store_fields,)*
+        }
+
+        impl #store_name {
+            #(#accessors)*
+
+            /// Returns a snapshot of the whole value, subscribing the current
+            /// effect to every field.
+            #vis fn get(&self) -> #name
+            where
+                #(#get_bounds,)*
+            {
+                #name {
+                    #(#gets,)*
+                }
+            }
+
+            /// Replaces every field, notifying the subscribers of the fields
+            /// that changed once.
+            #vis fn set(&self, value: #name)
+            where
+                #(#set_bounds,)*
+            {
+                fenrix_core::batch(|| {
+                    #(#sets)*
+                });
+            }
+        }
+
+        impl fenrix_core::Store for #name {
+            type Store = #store_name;
+
+            fn into_store(self) -> #store_name {
+                #store_name {
+                    #(#inits,)*
+                }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
@@ -1,4 +1,4 @@
-use fenrix_core::{create_signal, provide_service, inject};
+use fenrix_core::{create_rw_signal, inject, provide_service, ReadSignal, WriteSignal};
 use std::collections::HashMap;
 use std::rc::Rc;
 use wasm_bindgen::prelude::*;
@@ -13,24 +13,24 @@ pub type Routable = fn() -> Node;
 pub struct Router {
     routes: Rc<HashMap<String, Routable>>,
     // The current path is a reactive signal.
-    pub current_path: Rc<dyn Fn() -> String>,
-    set_current_path: Rc<dyn Fn(String)>,
+    pub current_path: ReadSignal<String>,
+    set_current_path: WriteSignal<String>,
 }
 
 impl Router {
     pub fn new(routes: HashMap<String, Routable>) -> Self {
-        let (current_path, set_current_path) = create_signal(get_current_hash());
+        let (current_path, set_current_path) = create_rw_signal(get_current_hash()).split();
 
         let router = Self {
             routes: Rc::new(routes),
-            current_path: Rc::new(current_path),
-            set_current_path: Rc::new(set_current_path),
+            current_path,
+            set_current_path,
         };
 
         // Listen for hash changes to update the current_path signal.
-        let router_clone = router.clone();
+        let set_current_path = router.set_current_path;
         let on_hash_change = Closure::wrap(Box::new(move |_: HashChangeEvent| {
-            (router_clone.set_current_path)(get_current_hash());
+            set_current_path.set(get_current_hash());
         }) as Box<dyn FnMut(_)>);
 
         window()
@@ -1,4 +1,4 @@
-use fenrix_core::{inject, provide_service};
+use fenrix_core::{inject, provide_context, provide_service};
 use fenrix_dom::render;
 use fenrix_macros::{component, rsx};
 use std::rc::Rc;
@@ -26,20 +26,44 @@ impl LoggerService {
 // 2. Define a component that uses the service.
 #[allow(non_snake_case)]
 #[component]
-fn App() -> Node {
+fn LogButton() -> Node {
     // 3. Inject the service.
-    // The `inject` hook looks up the service in the global container by its type.
+    // The `inject` hook looks the service up by its type, starting at the
+    // closest provider and ending at the global container.
     let logger: Rc<LoggerService> = inject();
 
     let on_button_click = move |_: MouseEvent| {
         logger.log("Button clicked! Message from injected service.");
     };
 
+    rsx! {
+        <button (click)={on_button_click}>"Log Message"</button>
+    }
+}
+
+// A component that gives its subtree its own logger, shadowing the global one.
+#[allow(non_snake_case)]
+#[component]
+fn AdminPanel() -> Node {
+    provide_context(LoggerService::new("ADMIN"));
+
+    rsx! {
+        <section>
+            <h2>"Admin"</h2>
+            <LogButton />
+        </section>
+    }
+}
+
+#[allow(non_snake_case)]
+#[component]
+fn App() -> Node {
     rsx! {
         <div>
             <h1>"Dependency Injection Example"</h1>
-            <p>"Click the button to see a message in the console from the injected logger service."</p>
-            <button (click)={on_button_click}>"Log Message"</button>
+            <p>"Click the buttons to see messages in the console from the injected logger services."</p>
+            <LogButton />
+            <AdminPanel />
         </div>
     }
 }
@@ -1,4 +1,4 @@
-use fenrix_core::{create_effect, use_state};
+use fenrix_core::{untrack, use_state, watch};
 use fenrix_dom::render;
 use fenrix_macros::{component, rsx};
 use fenrix_router::{provide_router, use_router, Routable};
@@ -28,16 +28,20 @@ fn NotFoundPage() -> Node {
 #[component]
 fn App() -> Node {
     let router = use_router();
-    let (active_component, set_active_component) = use_state::<Routable>(|| NotFoundPage);
+    // Read the initial path without subscribing whatever is rendering us.
+    let initial_path = untrack(|| router.current_path.get());
+    let initial_page = router.get_component(&initial_path).unwrap_or(NotFoundPage);
+    let (active_component, set_active_component) = use_state::<Routable>(move || initial_page);
 
-    // This effect listens to the router's `current_path` signal.
+    // `watch` only subscribes to the router's `current_path` signal.
     // When the path changes, it finds the corresponding component
     // and updates the `active_component` signal, causing a re-render.
-    let router_clone = router.clone();
-    create_effect(move || {
-        let path = (router_clone.current_path)();
+    // The callback itself runs untracked, so signals read while switching
+    // pages can never re-trigger the routing logic.
+    let current_path = router.current_path;
+    watch(move || current_path.get(), move |path, _| {
         web_sys::console::log_1(&format!("Routing effect running for path: {}", path).into());
-        let component = router_clone.get_component(&path).unwrap_or(NotFoundPage);
+        let component = router.get_component(path).unwrap_or(NotFoundPage);
         set_active_component(component);
     });
 
@@ -1,4 +1,4 @@
-use fenrix_core::create_signal;
+use fenrix_core::{create_resource, create_signal};
 use fenrix_macros::{component, rsx, server};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
@@ -25,24 +25,23 @@ pub async fn get_user_from_db(id: u32) -> Result<User, String> {
     }
 }
 
+#[allow(non_snake_case)]
 #[component]
 pub fn App() -> Node {
-    let (user, set_user) = create_signal(None::<User>);
+    // Nothing is requested until the button is clicked.
+    let (user_id, set_user_id) = create_signal(None::<u32>);
+    let user = create_resource(user_id.clone(), |id| async move {
+        match id {
+            Some(id) => get_user_from_db(id).await.map(Some),
+            None => Ok(None),
+        }
+    });
 
-    let handle_click = {
-        let set_user = set_user.clone();
-        move |_: web_sys::MouseEvent| {
-            let set_user = set_user.clone();
-            wasm_bindgen_futures::spawn_local(async move {
-                match get_user_from_db(1).await {
-                    Ok(fetched_user) => {
-                        set_user(Some(fetched_user));
-                    }
-                    Err(e) => {
-                        web_sys::console::error_1(&e.into());
-                    }
-                }
-            });
+    let handle_click = move |_: web_sys::MouseEvent| {
+        if user_id() == Some(1) {
+            user.refetch();
+        } else {
+            set_user_id(Some(1));
         }
     };
 
@@ -50,15 +49,27 @@ pub fn App() -> Node {
         <div>
             <h1>"Server Function Example"</h1>
             <button (click)={handle_click}>"Fetch User from Server"</button>
-            <div>
-                {
-                    if let Some(user) = user() {
-                        format!("Fetched User: {} (ID: {})", user.name, user.id)
-                    } else {
-                        "No user fetched yet.".to_string()
-                    }
-                }
-            </div>
+            <ErrorBoundary fallback={move |error, boundary| rsx! {
+                <div>
+                    <p>{format!("Error: {}", error)}</p>
+                    <button (click)={move |_: web_sys::MouseEvent| {
+                        user.refetch();
+                        boundary.reset();
+                    }}>"Try again"</button>
+                </div>
+            }}>
+                <Suspense fallback={|| rsx! { <div>"Loading..."</div> }}>
+                    <div>
+                        {
+                            if let Some(user) = user.read().flatten() {
+                                format!("Fetched User: {} (ID: {})", user.name, user.id)
+                            } else {
+                                "No user fetched yet.".to_string()
+                            }
+                        }
+                    </div>
+                </Suspense>
+            </ErrorBoundary>
         </div>
     }
 }
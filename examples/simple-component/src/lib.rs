@@ -1,7 +1,25 @@
-use fenrix_dom::render;
+use fenrix_dom::{render, Children, Slot};
 use fenrix_macros::{component, rsx};
 use wasm_bindgen::prelude::*;
 
+/// A component with props: a required `title`, an optional `footer` slot, and
+/// the children written between `<Card>` and `</Card>`.
+#[allow(non_snake_case)]
+#[component]
+fn Card(
+    #[prop(into)] title: String,
+    #[prop(optional)] footer: Slot,
+    children: Children,
+) -> web_sys::Node {
+    rsx! {
+        <section class="card">
+            <h2>{title.clone()}</h2>
+            {(children())}
+            <footer>{(footer.render_or(|| rsx! { <small>"No footer"</small> }))}</footer>
+        </section>
+    }
+}
+
 /// A simple, static component.
 #[allow(non_snake_case)]
 #[component]
@@ -10,6 +28,12 @@ fn App() -> web_sys::Node {
         <div>
             <h1>"Simple Component"</h1>
             <p>"This is rendered from a Fenrix component."</p>
+            <Card title="Props">
+                <p>"Components take props and children."</p>
+                <slot:footer>
+                    <small>"Passed from App"</small>
+                </slot:footer>
+            </Card>
         </div>
     }
 }
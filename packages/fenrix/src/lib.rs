@@ -7,18 +7,32 @@
 
 // Re-export core reactivity and component model
 pub use fenrix_core::{
-    create_effect, create_signal, inject, provide_service, use_effect, use_state, with_component_context,
+    batch, component_props, create_effect, create_history_signal, create_memo,
+    create_persisted_signal, create_persisted_signal_in, create_resource, create_root,
+    create_rw_signal, create_scope, create_signal, create_signal_map, create_signal_vec,
+    create_store, inject, inject_optional, on_cleanup, override_service, override_service_as,
+    provide_context, provide_factory, provide_service, provide_service_as, provide_transient,
+    throw_error, try_inject, untrack, use_context, use_effect, use_reducer,
+    use_reducer_with_middleware, use_state, watch, with_component_context,
+    with_named_component_context, with_test_container, CapturedError, Component, ComponentInstance,
+    Dependency, DiError, ErrorBoundaryContext, HistorySignal, HookError, HookSite, Injectable,
+    MapDiff, MemoryStorage, Middleware, Module, Props, ReadSignal, Resource, RwSignal, Scope,
+    ServerFnError, SignalMap, SignalVec, Storage, StorageListener, Store, SuspenseContext, VecDiff,
+    WriteSignal,
 };
 
 // Re-export DOM rendering
-pub use fenrix_dom::render;
+pub use fenrix_dom::{
+    create_reactive_view, error_boundary, for_each, render, render_signal_map, render_signal_vec,
+    show, suspense, switch, Children, Match, Slot, View, WebStorage,
+};
 
 // Re-export procedural macros
-pub use fenrix_macros::{component, rsx};
+pub use fenrix_macros::{component, injectable, module, rsx, Store};
 
 // Re-export router components
 pub use fenrix_router::{provide_router, use_router, Routable, Router};
 
 // Re-export common dependencies for convenience
 pub use wasm_bindgen;
-pub use web_sys;
\ No newline at end of file
+pub use web_sys;